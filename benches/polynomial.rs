@@ -0,0 +1,58 @@
+//! Speed of polynomial evaluation against the power sum it replaced
+//!
+//! Run with `cargo bench --bench polynomial`. Every evaluation scheme walks
+//! the same polynomial over the same inputs and the time per evaluation is
+//! printed next to the power sum, which needs a powf per coefficient.
+use piecewise_fcn_lib as pw;
+use pw::factory::interface::FunctionOutput;
+use pw::factory::polynomial::Polynomial;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Evaluations per scheme
+const SAMPLES: usize = 1_000_000;
+
+/// Power sum the polynomial was evaluated with before Horner's scheme
+fn power_sum(
+  coeff: &[f64],
+  x: f64,
+) -> f64 {
+  let mut out: f64 = 0.0;
+  for idx in coeff.iter().enumerate() {
+    out += idx.1 * x.powf(idx.0 as f64);
+  }
+  out
+}
+
+/// Nanoseconds per evaluation of f over [0,1) and the sum of its outputs
+fn time<F: Fn(f64) -> f64>(f: F) -> (f64, f64) {
+  let start = Instant::now();
+  let mut total = 0.0;
+  for step in 0..SAMPLES {
+    total += f(black_box(step as f64 / SAMPLES as f64));
+  }
+  (start.elapsed().as_nanos() as f64 / SAMPLES as f64, black_box(total))
+}
+
+fn main() {
+  for degree in [3, 7, 11, 15].iter() {
+    let coeff: Vec<f64> = (0..=*degree).map(|idx| 1.0 / (idx as f64 + 1.0)).collect();
+    let horner = Polynomial::new(1.0, (0.0, 1.0), coeff.clone(), false);
+    let compensated = Polynomial::new_compensated(1.0, (0.0, 1.0), coeff.clone(), false);
+
+    let power = time(|x| power_sum(&coeff, x));
+    let plain = time(|x| horner.generate(x).unwrap());
+    let exact = time(|x| compensated.generate(x).unwrap());
+    assert!((power.1 - plain.1).abs() < 1e-6 * power.1.abs());
+
+    println!(
+      "degree {:2}: power sum {:6.1} ns, horner {:6.1} ns ({:.1}x), compensated {:6.1} ns ({:.1}x)",
+      degree,
+      power.0,
+      plain.0,
+      power.0 / plain.0,
+      exact.0,
+      power.0 / exact.0,
+    );
+  }
+}
@@ -0,0 +1,17 @@
+/// Fourier coefficients of any function
+pub mod fourier;
+
+/// Roots and level crossings of any function
+pub mod roots;
+
+/// Extrema and range of any function
+pub mod extrema;
+
+/// Statistics of any function
+pub mod statistics;
+
+/// Sampled spectrum of any function
+pub mod spectrum;
+
+/// Arc length and curvature of any function
+pub mod geometry;
@@ -0,0 +1,273 @@
+use crate::factory::interface::{FunctionOutput, Piece};
+use crate::factory::polynomial::horner;
+use crate::numeric::float::next_down;
+use crate::numeric::poly;
+use crate::numeric::roots::polynomial_roots;
+use crate::operator::derivative;
+
+/// Samples per breakpoint interval used to find extrema numerically
+const SCAN: usize = 256;
+
+/// Which output of the function to analyse
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Order {
+  /// The output itself
+  Value,
+  /// First derivative of the output
+  Velocity,
+  /// Second derivative of the output
+  Acceleration,
+}
+
+/// Whether an extremum is a minimum or a maximum
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+  /// Smallest value nearby
+  Minimum,
+  /// Largest value nearby
+  Maximum,
+}
+
+/// Location and value of an extremum
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Extremum {
+  /// Minimum or maximum
+  pub kind: Kind,
+  /// Location of the extremum
+  pub x: f64,
+  /// Value at the extremum
+  pub value: f64,
+}
+
+/// Global and local extrema of a function
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extrema {
+  /// Smallest value over the limits
+  pub minimum: Extremum,
+  /// Largest value over the limits
+  pub maximum: Extremum,
+  /// Turning points inside of the breakpoint intervals, sorted by x
+  pub local: Vec<Extremum>,
+}
+
+/// Finds the global and local extrema of the value, velocity or acceleration
+///
+/// Functions with pieces are solved exactly: turning points are the roots of
+/// the derivative of every piece where the derivative changes sign.
+/// Everything else is scanned with 256 samples per breakpoint interval and
+/// every turning point is refined with a golden section search, with
+/// velocity and acceleration from finite differences.
+///
+/// Both ends of every breakpoint interval are candidates for the global
+/// extrema but not local ones. The limits are half open so the end of an
+/// interval is checked just before it, at its left limit. Returns None when
+/// the function is not defined anywhere inside of its limits.
+pub fn extrema(
+  fcn: &dyn FunctionOutput,
+  order: Order,
+) -> Option<Extrema> {
+  let order = match order {
+    Order::Value => 0,
+    Order::Velocity => 1,
+    Order::Acceleration => 2,
+  };
+  let (candidates, local) = match fcn.pieces() {
+    Some(pieces) => exact(&derivative::differentiate_pieces(&pieces, order)),
+    None => scan(fcn, order),
+  };
+  let first = *candidates.first()?;
+  let mut minimum = Extremum { kind: Kind::Minimum, x: first.0, value: first.1 };
+  let mut maximum = Extremum { kind: Kind::Maximum, x: first.0, value: first.1 };
+  for (x, value) in candidates.into_iter().chain(local.iter().map(|e| (e.x, e.value))) {
+    if value < minimum.value {
+      minimum = Extremum { kind: Kind::Minimum, x, value };
+    }
+    if value > maximum.value {
+      maximum = Extremum { kind: Kind::Maximum, x, value };
+    }
+  }
+  Some(Extrema { minimum, maximum, local })
+}
+
+/// Bounding box ((x min, x max), (y min, y max)) of a function
+///
+/// The x range is the limits and the y range is the global extrema of the
+/// output, so a stacked Function tree is bounded by what it actually
+/// produces rather than by adding up the ranges of its parts.
+pub fn bounding_box(fcn: &dyn FunctionOutput) -> Option<((f64, f64), (f64, f64))> {
+  let out = extrema(fcn, Order::Value)?;
+  Some((fcn.get_limits(), (out.minimum.value, out.maximum.value)))
+}
+
+/// Piece end points and derivative roots of every piece
+fn exact(pieces: &[Piece]) -> (Vec<(f64, f64)>, Vec<Extremum>) {
+  let mut candidates = Vec::new();
+  let mut local = Vec::new();
+  for piece in pieces.iter() {
+    let width = piece.end - piece.start;
+    if width <= 0.0 {
+      continue;
+    }
+    let coeff = &piece.coefficients;
+    let end = next_down(piece.end);
+    candidates.push((piece.start, horner(coeff, 0.0)));
+    candidates.push((end, horner(coeff, end - piece.start)));
+
+    let slope = poly::derivative(coeff);
+    if slope.iter().all(|c| *c == 0.0) {
+      continue;
+    }
+    let mut points = vec![0.0];
+    points.extend(polynomial_roots(&slope, (0.0, width)).into_iter().filter(|x| (*x > 0.0) && (*x < width)));
+    points.push(width);
+    // Sign of the slope between neighbouring critical points
+    let signs: Vec<f64> = points.windows(2).map(|w| horner(&slope, 0.5 * (w[0] + w[1])).signum()).collect();
+    for (idx, x) in points.iter().enumerate().skip(1).take(points.len() - 2) {
+      let kind = match (signs[idx - 1] > 0.0, signs[idx] > 0.0) {
+        (true, false) => Kind::Maximum,
+        (false, true) => Kind::Minimum,
+        _ => continue,
+      };
+      local.push(Extremum { kind, x: piece.start + x, value: horner(coeff, *x) });
+    }
+  }
+  (candidates, local)
+}
+
+/// Samples every breakpoint interval and refines the turning points
+fn scan(
+  fcn: &dyn FunctionOutput,
+  order: usize,
+) -> (Vec<(f64, f64)>, Vec<Extremum>) {
+  let breakpoints = fcn.breakpoints();
+  let value = |x: f64| derivative::numeric(fcn, &breakpoints, x, order);
+  let mut candidates = Vec::new();
+  let mut local = Vec::new();
+  for window in breakpoints.windows(2) {
+    let (a, b) = (window[0], window[1]);
+    if b <= a {
+      continue;
+    }
+    let samples: Vec<(f64, f64)> = (0..=SCAN)
+      .filter_map(|step| {
+        let x = if step == SCAN { next_down(b) } else { a + (b - a) * step as f64 / SCAN as f64 };
+        value(x).map(|y| (x, y))
+      })
+      .collect();
+    if let (Some(first), Some(last)) = (samples.first(), samples.last()) {
+      candidates.push(*first);
+      candidates.push(*last);
+    }
+    for w in samples.windows(3) {
+      let (left, mid, right) = (w[0], w[1], w[2]);
+      let kind = if (left.1 < mid.1) && (mid.1 >= right.1) {
+        Kind::Maximum
+      } else if (left.1 > mid.1) && (mid.1 <= right.1) {
+        Kind::Minimum
+      } else {
+        continue;
+      };
+      let sign = if kind == Kind::Maximum { -1.0 } else { 1.0 };
+      let x = golden(&|x: f64| value(x).map_or(f64::INFINITY, |y| sign * y), left.0, right.0);
+      let refined = value(x).unwrap_or(mid.1);
+      // Keep the sample if refining wandered off onto a worse point
+      let (x, refined) = if sign * refined <= sign * mid.1 { (x, refined) } else { mid };
+      local.push(Extremum { kind, x, value: refined });
+    }
+  }
+  (candidates, local)
+}
+
+/// Minimizes f over [a,b] with a golden section search
+fn golden<F>(
+  f: &F,
+  mut a: f64,
+  mut b: f64,
+) -> f64
+where
+  F: Fn(f64) -> f64,
+{
+  let ratio = 0.5 * (5.0f64.sqrt() - 1.0);
+  let mut c = b - ratio * (b - a);
+  let mut d = a + ratio * (b - a);
+  let (mut fc, mut fd) = (f(c), f(d));
+  // Shrinks the bracket by the ratio every step, 80 steps reach adjacent floats
+  for _ in 0..80 {
+    if fc < fd {
+      b = d;
+      d = c;
+      fd = fc;
+      c = b - ratio * (b - a);
+      fc = f(c);
+    } else {
+      a = c;
+      c = d;
+      fc = fd;
+      d = a + ratio * (b - a);
+      fd = f(d);
+    }
+  }
+  0.5 * (a + b)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  #[test]
+  fn polynomial() {
+    // x^3 - 3x over [-2,2)
+    let factory = Factory;
+    let a = factory.polynomial(4.0, (-2.0, 2.0), vec![0.0, -3.0, 0.0, 1.0], false);
+    let b = extrema(a.as_ref(), Order::Value).unwrap();
+    assert_eq!(2, b.local.len());
+    assert_eq!(Kind::Maximum, b.local[0].kind);
+    assert!((b.local[0].x - 1.0).abs() < 1e-12);
+    assert!((b.local[0].value - 2.0).abs() < 1e-12);
+    assert_eq!(Kind::Minimum, b.local[1].kind);
+    assert!((b.local[1].x - 3.0).abs() < 1e-12);
+    assert_eq!(0.0, b.minimum.x);
+    assert_eq!(-2.0, b.minimum.value);
+    // Velocity 3x^2 - 3 is smallest at the middle
+    let c = extrema(a.as_ref(), Order::Velocity).unwrap();
+    assert!((c.minimum.x - 2.0).abs() < 1e-12);
+    assert!((c.minimum.value + 3.0).abs() < 1e-12);
+    assert_eq!(Kind::Minimum, c.local[0].kind);
+  }
+  #[test]
+  fn trapezoid() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.smoothstep(1.0, (0.0, 1.0)));
+    a.add_subfunction(factory.constant(1.0, 1.0));
+    a.add_subfunction(factory.smoothstep(1.0, (1.0, 0.0)));
+    let b = extrema(&a, Order::Velocity).unwrap();
+    assert!((b.maximum.x - 0.5).abs() < 1e-12);
+    assert!((b.maximum.value - 1.5).abs() < 1e-12);
+    assert!((b.minimum.x - 2.5).abs() < 1e-12);
+    let c = extrema(&a, Order::Acceleration).unwrap();
+    assert_eq!(6.0, c.maximum.value);
+    assert_eq!(-6.0, c.minimum.value);
+    assert_eq!(((0.0, 3.0), (0.0, 1.0)), bounding_box(&a).unwrap());
+  }
+  #[test]
+  fn numeric() {
+    let factory = Factory;
+    let a = factory.closure(6.0, (0.0, 6.0), |x| x.sin());
+    let b = extrema(a.as_ref(), Order::Value).unwrap();
+    assert_eq!(2, b.local.len());
+    assert!((b.local[0].x - std::f64::consts::FRAC_PI_2).abs() < 1e-7);
+    assert!((b.maximum.value - 1.0).abs() < 1e-14);
+    assert!((b.minimum.value + 1.0).abs() < 1e-14);
+    let c = extrema(a.as_ref(), Order::Velocity).unwrap();
+    assert!((c.local[0].x - std::f64::consts::PI).abs() < 1e-5);
+    assert!((c.maximum.value - 1.0).abs() < 1e-8);
+  }
+  #[test]
+  fn undefined() {
+    let a = Function::new();
+    assert_eq!(None, extrema(&a, Order::Value));
+  }
+}
@@ -0,0 +1,80 @@
+use crate::factory::fourier::Fourier;
+use crate::factory::interface::FunctionOutput;
+use crate::numeric::quadrature::integrate;
+use std::f64::consts::PI;
+
+/// Extracts the Fourier series of a function over its limits
+///
+/// The function is treated as one period, so the fundamental frequency is one
+/// over the width of its limits, and the first `harmonics` harmonics are
+/// found with adaptive quadrature. The returned series has the same duration
+/// as the function and starts at zero, so a function that starts somewhere
+/// else lines up with the series once it is given the same delay. Gaps where
+/// the function returns None count as zero.
+pub fn coefficients(
+  fcn: &dyn FunctionOutput,
+  harmonics: usize,
+) -> Fourier {
+  let (start, stop) = fcn.get_limits();
+  let period = stop - start;
+  let value = |x: f64| fcn.generate(x).unwrap_or(0.0);
+  let tol = 1e-10 * period;
+
+  let offset = integrate(&value, start, stop, tol) / period;
+  let mut cosine = Vec::with_capacity(harmonics);
+  let mut sine = Vec::with_capacity(harmonics);
+  for n in 1..=harmonics {
+    let omega = 2.0 * PI * n as f64 / period;
+    let a = integrate(&|x: f64| value(x) * (omega * (x - start)).cos(), start, stop, tol);
+    let b = integrate(&|x: f64| value(x) * (omega * (x - start)).sin(), start, stop, tol);
+    cosine.push(2.0 * a / period);
+    sine.push(2.0 * b / period);
+  }
+  Fourier::new(period, 1.0 / period, offset, cosine, sine)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  #[test]
+  fn round_trip() {
+    let a = Fourier::new(2.0, 0.5, 1.0, vec![0.5, 0.0, -0.25], vec![1.0, 0.75]);
+    let b = coefficients(&a, 4);
+    assert!((b.offset() - 1.0).abs() < 1e-9);
+    for (lhs, rhs) in b.cosine().iter().zip([0.5, 0.0, -0.25, 0.0].iter()) {
+      assert!((lhs - rhs).abs() < 1e-9);
+    }
+    for (lhs, rhs) in b.sine().iter().zip([1.0, 0.75, 0.0, 0.0].iter()) {
+      assert!((lhs - rhs).abs() < 1e-9);
+    }
+  }
+  #[test]
+  fn square_wave() {
+    // Square wave has only odd sine harmonics of 4 / (pi n)
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.constant(0.5, 1.0));
+    a.add_subfunction(factory.constant(0.5, -1.0));
+    let b = coefficients(&a, 5);
+    assert!(b.offset().abs() < 1e-9);
+    for (n, s) in b.sine().iter().enumerate() {
+      let harmonic = (n + 1) as f64;
+      let expected = if n % 2 == 0 { 4.0 / (PI * harmonic) } else { 0.0 };
+      assert!((s - expected).abs() < 1e-8);
+      assert!(b.cosine()[n].abs() < 1e-8);
+    }
+  }
+  #[test]
+  fn delayed() {
+    // The series is relative to the start of the function
+    let factory = Factory;
+    let mut a = Function::new_delay(3.0);
+    a.add_subfunction(factory.closure(1.0, (0.0, 2.0 * PI), f64::sin));
+    let b = coefficients(&a, 2);
+    assert!((b.sine()[0] - 1.0).abs() < 1e-9);
+    assert!(b.cosine()[0].abs() < 1e-9);
+  }
+}
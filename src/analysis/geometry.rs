@@ -0,0 +1,88 @@
+use crate::factory::interface::FunctionOutput;
+use crate::numeric::quadrature::integrate;
+use crate::operator::derivative::derivative_of;
+
+/// Length of the curve y = f(x) over the limits
+///
+/// See [`arc_length_over`].
+pub fn arc_length(fcn: &dyn FunctionOutput) -> f64 {
+  arc_length_over(fcn, fcn.get_limits())
+}
+
+/// Length of the curve y = f(x) over the range [start,end)
+///
+/// The integral of sqrt(1 + f'(x)^2) is found with adaptive Gauss-Kronrod
+/// quadrature between every pair of breakpoints. The slope is exact for
+/// functions with pieces and from finite differences otherwise. Jumps at
+/// breakpoints are not part of the curve so they add nothing to its length.
+pub fn arc_length_over(
+  fcn: &dyn FunctionOutput,
+  range: (f64, f64),
+) -> f64 {
+  let slope = derivative_of(fcn, 1);
+  let speed = |x: f64| slope(x).unwrap_or(0.0).hypot(1.0);
+  let mut points = vec![range.0, range.1];
+  points.extend(fcn.breakpoints());
+  points.retain(|x| (*x >= range.0) && (*x <= range.1));
+  points.sort_by(|a, b| a.total_cmp(b));
+  points.dedup();
+  points.windows(2).map(|w| integrate(&speed, w[0], w[1], 1e-12 * (w[1] - w[0]))).sum()
+}
+
+/// Signed curvature of the curve y = f(x) at x
+///
+/// The curvature f'' / (1 + f'^2)^(3/2) is one over the radius of the circle
+/// that best fits the curve at x, positive where the curve bends upwards.
+/// Returns None outside of the limits.
+pub fn curvature(
+  fcn: &dyn FunctionOutput,
+  x: f64,
+) -> Option<f64> {
+  let slope = derivative_of(fcn, 1)(x)?;
+  let bend = derivative_of(fcn, 2)(x)?;
+  Some(bend / (1.0 + slope * slope).powf(1.5))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  #[test]
+  fn line() {
+    let factory = Factory;
+    let a = factory.ramp(3.0, (0.0, 4.0));
+    assert!((arc_length(a.as_ref()) - 5.0).abs() < 1e-14);
+    assert!((arc_length_over(a.as_ref(), (0.0, 1.5)) - 2.5).abs() < 1e-14);
+    assert_eq!(Some(0.0), curvature(a.as_ref(), 1.0));
+  }
+  #[test]
+  fn circle() {
+    // Upper half of the unit circle, away from its vertical ends
+    let factory = Factory;
+    let a = factory.closure(1.6, (-0.8, 0.8), |x| (1.0 - x * x).sqrt());
+    let expected = 2.0 * 0.8f64.asin();
+    assert!((arc_length(a.as_ref()) - expected).abs() < 1e-9);
+    assert!((curvature(a.as_ref(), 0.8).unwrap() + 1.0).abs() < 1e-5);
+    assert!((curvature(a.as_ref(), 0.2).unwrap() + 1.0).abs() < 1e-5);
+  }
+  #[test]
+  fn parabola() {
+    // y = x^2 has curvature 2 at the bottom and length known in closed form
+    let factory = Factory;
+    let a = factory.polynomial(1.0, (0.0, 1.0), vec![0.0, 0.0, 1.0], false);
+    let expected = 0.5 * 5.0f64.sqrt() + 0.25 * (2.0 + 5.0f64.sqrt()).ln();
+    assert!((arc_length(a.as_ref()) - expected).abs() < 1e-12);
+    assert_eq!(Some(2.0), curvature(a.as_ref(), 0.0));
+    assert_eq!(None, curvature(a.as_ref(), 1.0));
+  }
+  #[test]
+  fn jump() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.constant(1.0, 0.0));
+    a.add_subfunction(factory.constant(1.0, 5.0));
+    assert!((arc_length(&a) - 2.0).abs() < 1e-14);
+  }
+}
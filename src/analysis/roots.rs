@@ -0,0 +1,254 @@
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use crate::factory::polynomial::horner;
+use crate::numeric::brent::brent;
+use crate::numeric::float::next_down;
+use crate::numeric::roots::polynomial_roots;
+
+/// Samples per breakpoint interval used to bracket crossings numerically
+const SCAN: usize = 256;
+
+/// Place where a function meets a level
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Crossing {
+  /// The function equals the level at x
+  Root(f64),
+  /// The function jumps from one side of the level to the other at x
+  Jump(f64),
+}
+
+impl Crossing {
+  /// Location of the crossing
+  pub fn x(&self) -> f64 {
+    match self {
+      Crossing::Root(x) | Crossing::Jump(x) => *x,
+    }
+  }
+}
+
+/// Finds every x inside of the limits where the function meets the level
+///
+/// Functions with pieces are solved exactly: the roots of every piece minus
+/// the level are found with the polynomial root finder, so touching roots
+/// that do not change sign are found too. Everything else is scanned with
+/// 256 samples per breakpoint interval and every sign change is narrowed
+/// down with Brent's method, see [`crossings_scan`].
+///
+/// Wherever the output jumps over the level a Jump is reported, the
+/// function never equals the level there. A stretch that
+/// sits exactly on the level is reported once at its start. The crossings
+/// are sorted so the first one is the first time the level is reached.
+pub fn crossings(
+  fcn: &dyn FunctionOutput,
+  level: f64,
+) -> Vec<Crossing> {
+  match fcn.pieces() {
+    Some(pieces) => {
+      let mut out = Vec::new();
+      let mut left: Option<f64> = None;
+      let mut on_level = false;
+      for piece in pieces.iter() {
+        let mut coeff = piece.coefficients.clone();
+        // An empty polynomial is zero everywhere
+        if coeff.is_empty() {
+          coeff.push(0.0);
+        }
+        coeff[0] -= level;
+        let width = piece.end - piece.start;
+        if let Some(left) = left {
+          if left * coeff[0] < 0.0 {
+            out.push(Crossing::Jump(piece.start));
+          }
+        }
+        let flat = coeff.iter().all(|c| *c == 0.0);
+        // A stretch on the level continues from the piece before it
+        let skip = if on_level { 0.0 } else { -1.0 };
+        for x in polynomial_roots(&coeff, (0.0, width)) {
+          if (x < width) && (x > skip) {
+            out.push(Crossing::Root(piece.start + x));
+          }
+        }
+        on_level = flat;
+        left = Some(horner(&coeff, width));
+      }
+      out
+    }
+    None => scan_crossings(fcn, level, SCAN),
+  }
+}
+
+/// Finds crossings numerically with a given number of samples per breakpoint interval
+///
+/// Crossings are bracketed by sign changes between neighbouring samples so
+/// two crossings closer together than the sample spacing, or a root that
+/// only touches the level, can be missed. Raise the number of samples for
+/// quickly changing functions. Places where the function is None are
+/// skipped. It is an error for there to be no samples per interval.
+pub fn crossings_scan(
+  fcn: &dyn FunctionOutput,
+  level: f64,
+  scan: usize,
+) -> Result<Vec<Crossing>, Error> {
+  if scan == 0 {
+    return Err(Error::InvalidInput("scan needs at least one sample per interval"));
+  }
+  Ok(scan_crossings(fcn, level, scan))
+}
+
+/// Scans for crossings with a number of samples that is known to be positive
+fn scan_crossings(
+  fcn: &dyn FunctionOutput,
+  level: f64,
+  scan: usize,
+) -> Vec<Crossing> {
+  let value = |x: f64| fcn.generate(x).map(|y| y - level);
+  let mut out = Vec::new();
+  // Last sample of the interval before, which is the left limit at its end
+  let mut left: Option<f64> = None;
+  let mut on_level = false;
+  for window in fcn.breakpoints().windows(2) {
+    let (a, b) = (window[0], window[1]);
+    if b <= a {
+      continue;
+    }
+    let mut prev: Option<(f64, f64)> = None;
+    for step in 0..=scan {
+      let x = if step == scan { next_down(b) } else { a + (b - a) * step as f64 / scan as f64 };
+      let y = match value(x) {
+        Some(y) => y,
+        None => {
+          prev = None;
+          on_level = false;
+          continue;
+        }
+      };
+      if step == 0 {
+        if let Some(l) = left {
+          if l * y < 0.0 {
+            out.push(Crossing::Jump(x));
+          }
+        }
+      }
+      if y == 0.0 {
+        if !on_level {
+          out.push(Crossing::Root(x));
+        }
+        on_level = true;
+      } else {
+        if let Some((px, py)) = prev {
+          if py * y < 0.0 {
+            out.push(bracketed(&|x: f64| value(x).unwrap_or(0.0), (px, py), (x, y)));
+          }
+        }
+        on_level = false;
+      }
+      prev = Some((x, y));
+    }
+    left = prev.map(|p| p.1);
+  }
+  out
+}
+
+/// Narrows a sign change down and tells roots apart from jumps
+///
+/// Brent's method closes in on a discontinuity just like it does on a root,
+/// the difference is that the output there is still far from the level.
+fn bracketed<F>(
+  value: &F,
+  a: (f64, f64),
+  b: (f64, f64),
+) -> Crossing
+where
+  F: Fn(f64) -> f64,
+{
+  let x = brent(value, a.0, b.0);
+  if value(x).abs() > 1e-6 * a.1.abs().max(b.1.abs()) {
+    Crossing::Jump(x)
+  } else {
+    Crossing::Root(x)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  #[test]
+  fn polynomial() {
+    // (x - 1)(x - 2) over [0,3)
+    let factory = Factory;
+    let a = factory.polynomial(3.0, (0.0, 3.0), vec![2.0, -3.0, 1.0], false);
+    let b = crossings(a.as_ref(), 0.0);
+    assert_eq!(2, b.len());
+    assert!((b[0].x() - 1.0).abs() < 1e-14);
+    assert!((b[1].x() - 2.0).abs() < 1e-14);
+    // Touching the minimum of -1/4 at 1.5
+    let c = crossings(a.as_ref(), -0.25);
+    assert_eq!(1, c.len());
+    assert!((c[0].x() - 1.5).abs() < 1e-7);
+  }
+  #[test]
+  fn jumps() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+    a.add_subfunction(factory.constant(1.0, -1.0));
+    a.add_subfunction(factory.ramp(1.0, (-1.0, 1.0)));
+    let b = crossings(&a, 0.5);
+    assert_eq!(vec![Crossing::Root(0.5), Crossing::Jump(1.0), Crossing::Root(2.75)], b);
+    // The same function without pieces finds the same crossings
+    let mut c = Function::new();
+    c.add_subfunction(factory.closure(1.0, (0.0, 1.0), |x| x));
+    c.add_subfunction(factory.constant(1.0, -1.0));
+    c.add_subfunction(factory.closure(1.0, (-1.0, 1.0), |x| x));
+    let d = crossings(&c, 0.5);
+    assert_eq!(3, d.len());
+    assert!((d[0].x() - 0.5).abs() < 1e-15);
+    assert_eq!(Crossing::Jump(1.0), d[1]);
+    assert!((d[2].x() - 2.75).abs() < 1e-15);
+  }
+  #[test]
+  fn flat() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+    a.add_subfunction(factory.constant(1.0, 1.0));
+    a.add_subfunction(factory.constant(1.0, 1.0));
+    assert_eq!(vec![Crossing::Root(1.0)], crossings(&a, 1.0));
+    assert_eq!(vec![Crossing::Root(1.0)], crossings_scan(&a, 1.0, 16).unwrap());
+  }
+  #[test]
+  fn empty_polynomial() {
+    // A polynomial without coefficients is zero everywhere
+    let factory = Factory;
+    let a = factory.polynomial(1.0, (0.0, 1.0), vec![], false);
+    assert!(crossings(a.as_ref(), 0.5).is_empty());
+    assert_eq!(vec![Crossing::Root(0.0)], crossings(a.as_ref(), 0.0));
+  }
+  #[test]
+  fn no_scan() {
+    let factory = Factory;
+    let a = factory.ramp(1.0, (0.0, 1.0));
+    assert!(matches!(crossings_scan(a.as_ref(), 0.5, 0).err(), Some(Error::InvalidInput(_))));
+  }
+  #[test]
+  fn closure() {
+    let factory = Factory;
+    let a = factory.closure(10.0, (0.0, 10.0), |x| x.sin());
+    let b = crossings(a.as_ref(), 0.5);
+    assert_eq!(4, b.len());
+    assert!((b[0].x() - 0.5f64.asin()).abs() < 1e-14);
+    assert!((b[1].x() - (std::f64::consts::PI - 0.5f64.asin())).abs() < 1e-14);
+  }
+  #[test]
+  fn step() {
+    // Left-continuous step has no pieces but its edge is a breakpoint
+    let factory = Factory;
+    let a = factory.step(2.0, 1.0, (0.0, 1.0), 0.0);
+    let b = crossings(a.as_ref(), 0.5);
+    assert_eq!(1, b.len());
+    assert!(matches!(b[0], Crossing::Jump(x) if (x - 1.0).abs() < 1e-15));
+  }
+}
@@ -0,0 +1,162 @@
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use crate::numeric::fft::fft;
+use crate::sampling::stream::{Missing, Sample};
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+/// Window applied to the samples before the transform
+///
+/// A window tapers the ends of the samples so a frequency that does not fit
+/// a whole number of times into them leaks less energy into other bins.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Window {
+  /// No window, the narrowest peaks but the most leakage
+  Rectangular,
+  /// Raised cosine, a good default
+  Hann,
+  /// Raised cosine that cancels the nearest side lobe
+  Hamming,
+  /// Three term cosine with very low leakage and wide peaks
+  Blackman,
+}
+
+impl Window {
+  /// Weight of sample n out of len
+  fn weight(
+    self,
+    n: usize,
+    len: usize,
+  ) -> f64 {
+    if len <= 1 {
+      return 1.0;
+    }
+    // Periodic windows, they line up with the bins of the transform
+    let phase = 2.0 * PI * n as f64 / len as f64;
+    match self {
+      Window::Rectangular => 1.0,
+      Window::Hann => 0.5 - 0.5 * phase.cos(),
+      Window::Hamming => 0.54 - 0.46 * phase.cos(),
+      Window::Blackman => 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos(),
+    }
+  }
+}
+
+/// Frequency bin of a spectrum
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bin {
+  /// Frequency in cycles per unit of x
+  pub frequency: f64,
+  /// Amplitude of the sinusoid at this frequency
+  pub magnitude: f64,
+  /// Phase in radians of a cosine at the start of the samples
+  pub phase: f64,
+}
+
+/// Computes the one-sided spectrum of a function sampled at a rate
+///
+/// The function is sampled exactly like write_to_file, floor(width * rate)
+/// samples from the start of its limits with missing values counted as zero. The
+/// samples are windowed and transformed and the bins run from zero to half
+/// the rate. The magnitude is scaled by the sum of the window so a sinusoid
+/// that lands on a bin reads its own amplitude, a * cos(2 pi f x + phase)
+/// shows up as magnitude a and the phase. It is an error for the rate not to
+/// be positive.
+pub fn spectrum(
+  fcn: &dyn FunctionOutput,
+  sample_rate_hz: f64,
+  window: Window,
+) -> Result<Vec<Bin>, Error> {
+  let values: Vec<f64> = fcn.samples(sample_rate_hz)?.missing(Missing::Fill(0.0)).map(|s| s.1).collect();
+  let n = values.len();
+  let mut samples = Vec::with_capacity(n);
+  let mut gain = 0.0;
+  for (step, value) in values.iter().enumerate() {
+    let weight = window.weight(step, n);
+    samples.push((weight * value, 0.0));
+    gain += weight;
+  }
+  Ok(
+    fft(&samples)
+      .iter()
+      .take(n / 2 + 1)
+      .enumerate()
+      .map(|(k, x)| {
+        // Everything but zero and the Nyquist frequency is split with its mirror image
+        let fold = if (k == 0) || (2 * k == n) { 1.0 } else { 2.0 };
+        Bin {
+          frequency: k as f64 * sample_rate_hz / n as f64,
+          magnitude: fold * x.0.hypot(x.1) / gain,
+          phase: x.1.atan2(x.0),
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Utility for writing a spectrum to a file
+///
+/// Every line is frequency,magnitude,phase.
+pub fn write_to_file(
+  bins: &[Bin],
+  file_name: String,
+) {
+  let path = Path::new(&file_name);
+  let display = path.display();
+
+  let mut file = match File::create(path) {
+    Err(why) => panic!("Couldn't create {}: {}", display, why),
+    Ok(file) => file,
+  };
+
+  for bin in bins.iter() {
+    if let Err(why) = file.write_all(format!("{},{},{}\n", bin.frequency, bin.magnitude, bin.phase).as_bytes()) {
+      panic!("Couldn't write to {}: {}", display, why);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+
+  #[test]
+  fn sinusoids() {
+    // Offset of 1, 2 cos at 5 Hz and 0.5 sin at 12 Hz over one second
+    let factory = Factory;
+    let mut sine = vec![0.0; 12];
+    sine[11] = 0.5;
+    let a = factory.fourier(1.0, 1.0, 1.0, vec![0.0, 0.0, 0.0, 0.0, 2.0], sine);
+    let b = spectrum(a.as_ref(), 100.0, Window::Rectangular).unwrap();
+    assert_eq!(51, b.len());
+    assert_eq!(5.0, b[5].frequency);
+    assert!((b[0].magnitude - 1.0).abs() < 1e-12);
+    assert!((b[5].magnitude - 2.0).abs() < 1e-12);
+    assert!(b[5].phase.abs() < 1e-12);
+    assert!((b[12].magnitude - 0.5).abs() < 1e-12);
+    assert!((b[12].phase + PI / 2.0).abs() < 1e-12);
+    assert!(b.iter().enumerate().filter(|(k, _)| ![0, 5, 12].contains(k)).all(|(_, bin)| bin.magnitude < 1e-12));
+  }
+  #[test]
+  fn windows() {
+    // A bin centered sinusoid keeps its amplitude under every window
+    let factory = Factory;
+    let a = factory.fourier(2.0, 4.0, 0.0, vec![3.0], vec![]);
+    for window in [Window::Rectangular, Window::Hann, Window::Hamming, Window::Blackman].iter() {
+      let b = spectrum(a.as_ref(), 64.0, *window).unwrap();
+      assert!((b[8].magnitude - 3.0).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn leakage() {
+    // Between bins the Hann window leaks far less into distant bins
+    let factory = Factory;
+    let a = factory.fourier(1.0, 10.5, 0.0, vec![1.0], vec![]);
+    let b = spectrum(a.as_ref(), 128.0, Window::Rectangular).unwrap();
+    let c = spectrum(a.as_ref(), 128.0, Window::Hann).unwrap();
+    assert!(c[30].magnitude < 0.01 * b[30].magnitude);
+  }
+}
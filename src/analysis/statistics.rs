@@ -0,0 +1,274 @@
+use crate::analysis::extrema::{self, Order};
+use crate::analysis::roots;
+use crate::error::Error;
+use crate::factory::interface::{FunctionOutput, Piece};
+use crate::factory::polynomial::horner;
+use crate::numeric::float::next_down;
+use crate::numeric::poly;
+use crate::numeric::quadrature::integrate;
+use std::cmp::Ordering;
+
+/// Summary statistics of the output over a range
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+  /// Average value
+  pub mean: f64,
+  /// Root mean square value
+  pub rms: f64,
+  /// Average squared distance from the mean
+  pub variance: f64,
+  /// Integral of the squared value
+  pub energy: f64,
+  /// Smallest value
+  pub minimum: f64,
+  /// Largest value
+  pub maximum: f64,
+  /// Largest minus smallest value
+  pub peak_to_peak: f64,
+}
+
+/// Computes the statistics of a function over its limits
+///
+/// See [`statistics_over`].
+pub fn statistics(fcn: &dyn FunctionOutput) -> Result<Statistics, Error> {
+  statistics_over(fcn, fcn.get_limits())
+}
+
+/// Computes the statistics of a function over the range [start,end)
+///
+/// Every x in the range counts the same, so the mean is the integral of the
+/// output divided by the width of the range. Functions with pieces are
+/// integrated exactly and everything else with adaptive Gauss-Kronrod
+/// quadrature between breakpoints. Places where the output is None count as
+/// zero. It is an error for the range not to end after it starts.
+pub fn statistics_over(
+  fcn: &dyn FunctionOutput,
+  range: (f64, f64),
+) -> Result<Statistics, Error> {
+  let (start, end) = range;
+  if end.partial_cmp(&start) != Some(Ordering::Greater) {
+    return Err(Error::InvalidInput("statistics need a range that ends after it starts"));
+  }
+  let width = end - start;
+  let (minimum, maximum) = value_range(fcn, range);
+  let (mean, energy, variance) = match fcn.pieces() {
+    Some(pieces) => {
+      let mean = exact(&pieces, range, &|c| c.to_vec()) / width;
+      let energy = exact(&pieces, range, &|c| poly::multiply(c, c));
+      let variance = exact(&pieces, range, &|c| {
+        let mut centered = c.to_vec();
+        // An empty polynomial is zero everywhere
+        if centered.is_empty() {
+          centered.push(0.0);
+        }
+        centered[0] -= mean;
+        poly::multiply(&centered, &centered)
+      }) / width;
+      (mean, energy, variance)
+    }
+    None => {
+      let value = |x: f64| fcn.generate(x).unwrap_or(0.0);
+      let mean = numeric(fcn, range, &value) / width;
+      let energy = numeric(fcn, range, &|x| value(x).powi(2));
+      let variance = numeric(fcn, range, &|x| (value(x) - mean).powi(2)) / width;
+      (mean, energy, variance)
+    }
+  };
+  Ok(Statistics {
+    mean,
+    rms: (energy / width).sqrt(),
+    variance,
+    energy,
+    minimum,
+    maximum,
+    peak_to_peak: maximum - minimum,
+  })
+}
+
+/// Finds a percentile of a function over its limits
+///
+/// See [`percentile_over`].
+pub fn percentile(
+  fcn: &dyn FunctionOutput,
+  fraction: f64,
+) -> Result<f64, Error> {
+  percentile_over(fcn, fcn.get_limits(), fraction)
+}
+
+/// Finds the value the output stays at or below for a fraction of the range
+///
+/// The fraction is between 0 and 1, so 0.5 is the median, 0 the minimum and
+/// 1 the maximum. The share of the range at or below a level is measured
+/// between the level crossings and the level is bisected until the share
+/// reaches the fraction. It is an error for the range not to end after it
+/// starts or for the fraction to be outside of [0,1].
+pub fn percentile_over(
+  fcn: &dyn FunctionOutput,
+  range: (f64, f64),
+  fraction: f64,
+) -> Result<f64, Error> {
+  if range.1.partial_cmp(&range.0) != Some(Ordering::Greater) {
+    return Err(Error::InvalidInput("percentile needs a range that ends after it starts"));
+  }
+  if !(0.0..=1.0).contains(&fraction) {
+    return Err(Error::InvalidInput("percentile fraction must be between 0 and 1"));
+  }
+  let (mut lo, mut hi) = value_range(fcn, range);
+  let target = fraction * (range.1 - range.0);
+  // The output may sit on its minimum for the whole fraction
+  if (fraction == 0.0) || (below(fcn, range, lo) >= target) {
+    return Ok(lo);
+  }
+  while hi - lo > 4.0 * f64::EPSILON * lo.abs().max(hi.abs()) {
+    let mid = 0.5 * (lo + hi);
+    if (mid <= lo) || (mid >= hi) {
+      break;
+    }
+    if below(fcn, range, mid) >= target {
+      hi = mid;
+    } else {
+      lo = mid;
+    }
+  }
+  Ok(hi)
+}
+
+/// Smallest and largest value inside of the range
+fn value_range(
+  fcn: &dyn FunctionOutput,
+  range: (f64, f64),
+) -> (f64, f64) {
+  let (start, end) = range;
+  let inside = |x: &f64| (*x >= start) && (*x < end);
+  let mut points = vec![start, next_down(end)];
+  for b in fcn.breakpoints().into_iter() {
+    points.push(b);
+    points.push(next_down(b));
+  }
+  if let Some(out) = extrema::extrema(fcn, Order::Value) {
+    points.extend(out.local.iter().map(|e| e.x));
+  }
+  let values: Vec<f64> = points.iter().filter(|x| inside(x)).map(|x| fcn.generate(*x).unwrap_or(0.0)).collect();
+  let minimum = values.iter().cloned().fold(f64::INFINITY, f64::min);
+  let maximum = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+  (minimum, maximum)
+}
+
+/// Width of the part of the range where the output is at or below the level
+fn below(
+  fcn: &dyn FunctionOutput,
+  range: (f64, f64),
+  level: f64,
+) -> f64 {
+  // The output can touch the level and leave it at a breakpoint without crossing
+  let mut points = vec![range.0, range.1];
+  points.extend(roots::crossings(fcn, level).iter().map(|c| c.x()));
+  points.extend(fcn.breakpoints());
+  points.retain(|x| (*x >= range.0) && (*x <= range.1));
+  points.sort_by(|a, b| a.total_cmp(b));
+  points.dedup();
+  points
+    .windows(2)
+    .filter(|w| fcn.generate(0.5 * (w[0] + w[1])).unwrap_or(0.0) <= level)
+    .map(|w| w[1] - w[0])
+    .sum()
+}
+
+/// Integrates a polynomial of every piece exactly over the part inside of the range
+fn exact(
+  pieces: &[Piece],
+  range: (f64, f64),
+  integrand: &dyn Fn(&[f64]) -> Vec<f64>,
+) -> f64 {
+  let mut out = 0.0;
+  for piece in pieces.iter() {
+    let (a, b) = (piece.start.max(range.0), piece.end.min(range.1));
+    if b > a {
+      let antiderivative = poly::integral(&integrand(&piece.coefficients));
+      out += horner(&antiderivative, b - piece.start) - horner(&antiderivative, a - piece.start);
+    }
+  }
+  out
+}
+
+/// Integrates numerically between the breakpoints inside of the range
+fn numeric(
+  fcn: &dyn FunctionOutput,
+  range: (f64, f64),
+  integrand: &dyn Fn(f64) -> f64,
+) -> f64 {
+  let mut points = vec![range.0];
+  points.extend(fcn.breakpoints().into_iter().filter(|x| (*x > range.0) && (*x < range.1)));
+  points.push(range.1);
+  let tol = 1e-10 * (range.1 - range.0);
+  points.windows(2).map(|w| integrate(&integrand, w[0], w[1], tol)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  #[test]
+  fn ramp() {
+    // Uniformly distributed values between 0 and 2
+    let factory = Factory;
+    let a = factory.ramp(4.0, (0.0, 2.0));
+    let b = statistics(a.as_ref()).unwrap();
+    assert!((b.mean - 1.0).abs() < 1e-15);
+    assert!((b.energy - 16.0 / 3.0).abs() < 1e-14);
+    assert!((b.rms - (4.0f64 / 3.0).sqrt()).abs() < 1e-15);
+    assert!((b.variance - 1.0 / 3.0).abs() < 1e-15);
+    assert_eq!(0.0, b.minimum);
+    assert!((b.peak_to_peak - 2.0).abs() < 1e-15);
+    assert!((percentile(a.as_ref(), 0.25).unwrap() - 0.5).abs() < 1e-14);
+    assert!((percentile(a.as_ref(), 0.5).unwrap() - 1.0).abs() < 1e-14);
+  }
+  #[test]
+  fn sine() {
+    let factory = Factory;
+    let a = factory.fourier(2.0, 1.0, 0.0, vec![], vec![3.0]);
+    let b = statistics(a.as_ref()).unwrap();
+    assert!(b.mean.abs() < 1e-12);
+    assert!((b.rms - 3.0 / 2.0f64.sqrt()).abs() < 1e-12);
+    assert!((b.variance - 4.5).abs() < 1e-12);
+    assert!((b.peak_to_peak - 6.0).abs() < 1e-12);
+    // Half of a period sits above zero
+    assert!(percentile(a.as_ref(), 0.5).unwrap().abs() < 1e-12);
+    // sin is below 3 sin(pi/4) except for the middle half of every crest
+    assert!((percentile(a.as_ref(), 0.75).unwrap() - 3.0 * 0.5f64.sqrt()).abs() < 1e-9);
+  }
+  #[test]
+  fn sub_range() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.constant(1.0, 1.0));
+    a.add_subfunction(factory.constant(1.0, 3.0));
+    let b = statistics_over(&a, (0.5, 2.0)).unwrap();
+    assert!((b.mean - 7.0 / 3.0).abs() < 1e-15);
+    assert_eq!((1.0, 3.0), (b.minimum, b.maximum));
+    assert_eq!(1.0, percentile_over(&a, (0.5, 2.0), 0.3).unwrap());
+    assert_eq!(3.0, percentile_over(&a, (0.5, 2.0), 0.4).unwrap());
+    let c = statistics_over(&a, (1.25, 2.0)).unwrap();
+    assert_eq!(0.0, c.peak_to_peak);
+    assert!(c.variance.abs() < 1e-15);
+  }
+  #[test]
+  fn empty_polynomial() {
+    // A polynomial without coefficients is zero everywhere
+    let factory = Factory;
+    let a = factory.polynomial(1.0, (0.0, 1.0), vec![], false);
+    let b = statistics(a.as_ref()).unwrap();
+    assert_eq!((0.0, 0.0, 0.0), (b.mean, b.variance, b.peak_to_peak));
+  }
+  #[test]
+  fn invalid() {
+    let factory = Factory;
+    let a = factory.ramp(1.0, (0.0, 1.0));
+    assert!(matches!(statistics_over(a.as_ref(), (0.5, 0.5)).err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(statistics_over(a.as_ref(), (f64::NAN, 1.0)).err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(percentile_over(a.as_ref(), (1.0, 0.0), 0.5).err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(percentile(a.as_ref(), 1.5).err(), Some(Error::InvalidInput(_))));
+  }
+}
@@ -0,0 +1,5 @@
+/// Cardinal and Catmull-Rom splines through waypoints
+pub mod cardinal;
+
+/// Attack/decay/sustain/release envelopes
+pub mod adsr;
@@ -0,0 +1,120 @@
+use crate::error::Error;
+use crate::factory::closure::Closure;
+use crate::factory::interface::FunctionOutput;
+use crate::factory::primitive;
+use crate::function::Function;
+
+/// Shape of the attack, decay and release segments
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Curve {
+  /// Straight lines between the levels
+  Linear,
+  /// Exponential approach to the next level, like an analog RC envelope
+  ///
+  /// The value is the number of time constants that fit in a segment, larger
+  /// values change faster at the start of the segment and settle for longer.
+  /// The curve is rescaled so every segment still ends exactly on its level.
+  Exponential(f64),
+}
+
+/// Builds an attack/decay/sustain/release envelope
+///
+/// The envelope requires three inputs:
+/// * Times: (attack, decay, sustain, release) durations
+/// * Level: Sustain level relative to the peak of 1
+/// * Curve: Shape of the attack, decay and release segments
+///
+/// The envelope rises from 0 to 1 over the attack, falls to the sustain level
+/// over the decay, holds it for the sustain duration and falls back to 0 over
+/// the release. Segments with zero duration are left out so a zero attack
+/// starts right at the peak. The output is a Function of one subfunction per
+/// segment starting at 0. It is an error for a time to be negative or for an
+/// exponential curve not to have a positive number of time constants.
+///
+/// Multiply the envelope onto a carrier with
+/// [`Product`](crate::operator::product::Product) to shape a tone.
+pub fn adsr(
+  times: (f64, f64, f64, f64),
+  level: f64,
+  curve: Curve,
+) -> Result<Function, Error> {
+  let (attack, decay, sustain, release) = times;
+  if (attack < 0.0) || (decay < 0.0) || (sustain < 0.0) || (release < 0.0) {
+    return Err(Error::InvalidInput("envelope times must not be negative"));
+  }
+  if let Curve::Exponential(k) = curve {
+    if k <= 0.0 {
+      return Err(Error::InvalidInput("exponential envelope needs a positive number of time constants"));
+    }
+  }
+
+  let mut out = Function::new();
+  let segments = [(attack, (0.0, 1.0)), (decay, (1.0, level)), (sustain, (level, level)), (release, (level, 0.0))];
+  for (dur, values) in segments.iter() {
+    if *dur > 0.0 {
+      out.add_subfunction(segment(*dur, *values, curve));
+    }
+  }
+  Ok(out)
+}
+
+/// Single envelope segment from the start value to the end value
+fn segment(
+  dur: f64,
+  values: (f64, f64),
+  curve: Curve,
+) -> Box<dyn FunctionOutput> {
+  match curve {
+    Curve::Exponential(k) if values.0 != values.1 => {
+      let (start, end) = values;
+      let tail = (-k).exp();
+      Box::new(Closure::new(dur, (0.0, 1.0), move |t| {
+        end + (start - end) * ((-k * t).exp() - tail) / (1.0 - tail)
+      }))
+    }
+    _ => Box::new(primitive::ramp(dur, values)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn linear() {
+    let a = adsr((1.0, 1.0, 2.0, 1.0), 0.5, Curve::Linear).unwrap();
+    assert_eq!((0.0, 5.0), a.get_limits());
+    assert_eq!(Some(0.0), a.generate(0.0));
+    assert_eq!(Some(0.5), a.generate(0.5));
+    assert_eq!(Some(1.0), a.generate(1.0));
+    assert_eq!(Some(0.75), a.generate(1.5));
+    assert_eq!(Some(0.5), a.generate(3.0));
+    assert_eq!(Some(0.25), a.generate(4.5));
+    assert_eq!(None, a.generate(5.0));
+  }
+  #[test]
+  fn exponential() {
+    let a = adsr((1.0, 1.0, 1.0, 1.0), 0.5, Curve::Exponential(5.0)).unwrap();
+    // Every segment starts on its level and approaches the next one
+    assert_eq!(Some(0.0), a.generate(0.0));
+    assert!((a.generate(1.0).unwrap() - 1.0).abs() < 1e-15);
+    assert!((a.generate(2.0).unwrap() - 0.5).abs() < 1e-15);
+    assert_eq!(Some(0.5), a.generate(2.5));
+    assert!(a.generate(3.999).unwrap() < 1e-3);
+    // Faster than linear at the start of the attack
+    assert!(a.generate(0.2).unwrap() > 0.6);
+  }
+  #[test]
+  fn zero_attack() {
+    let a = adsr((0.0, 1.0, 1.0, 1.0), 0.5, Curve::Linear).unwrap();
+    assert_eq!((0.0, 3.0), a.get_limits());
+    assert_eq!(Some(1.0), a.generate(0.0));
+  }
+  #[test]
+  fn invalid() {
+    let a = adsr((1.0, -1.0, 1.0, 1.0), 0.5, Curve::Linear);
+    let b = adsr((1.0, 1.0, 1.0, 1.0), 0.5, Curve::Exponential(0.0));
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(b.err(), Some(Error::InvalidInput(_))));
+  }
+}
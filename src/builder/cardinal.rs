@@ -0,0 +1,157 @@
+use crate::error::Error;
+use crate::factory::hermite;
+use crate::function::Function;
+
+/// Spacing of the curve parameter between waypoints
+///
+/// The parameter spacing between two waypoints is the distance between them
+/// raised to a power. Centripetal spacing never forms cusps or self
+/// intersections inside of a segment and is usually the best choice.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Parameterization {
+  /// Equal spacing, the classic Catmull-Rom spline (power 0)
+  Uniform,
+  /// Square root of the distance (power 0.5)
+  Centripetal,
+  /// Distance between waypoints (power 1)
+  Chordal,
+}
+
+impl Parameterization {
+  fn alpha(self) -> f64 {
+    match self {
+      Parameterization::Uniform => 0.0,
+      Parameterization::Centripetal => 0.5,
+      Parameterization::Chordal => 1.0,
+    }
+  }
+}
+
+/// Builds a Catmull-Rom spline through the waypoints
+///
+/// Same as a cardinal spline with zero tension.
+pub fn catmull_rom(
+  x: &[f64],
+  y: &[f64],
+  parameterization: Parameterization,
+) -> Result<Function, Error> {
+  cardinal(x, y, 0.0, parameterization)
+}
+
+/// Builds a cardinal spline through the waypoints
+///
+/// The waypoints are (x,y) pairs with strictly increasing x and the spline
+/// passes through every one of them. Tangents are derived from the
+/// neighbouring waypoints using the parameter spacing, distances are measured
+/// in the (x,y) plane, and then scaled by (1 - tension). A tension of 0 is a
+/// Catmull-Rom spline and a tension of 1 stops at every waypoint. The end
+/// waypoints use a reflected phantom neighbour.
+///
+/// The output is a Function delayed to the first x with one cubic Hermite
+/// subfunction per waypoint interval. It is an error for there to be fewer
+/// than two waypoints, for x and y to differ in length or for x not to be
+/// strictly increasing.
+pub fn cardinal(
+  x: &[f64],
+  y: &[f64],
+  tension: f64,
+  parameterization: Parameterization,
+) -> Result<Function, Error> {
+  if x.len() < 2 {
+    return Err(Error::InvalidInput("spline needs at least two waypoints"));
+  }
+  if x.len() != y.len() {
+    return Err(Error::InvalidInput("waypoint x and y must be the same length"));
+  }
+  if !x.windows(2).all(|w| w[1] > w[0]) {
+    return Err(Error::InvalidInput("waypoint x must be strictly increasing"));
+  }
+
+  // Pad with reflected phantom waypoints so every segment has four points
+  let n = x.len();
+  let mut px = Vec::with_capacity(n + 2);
+  let mut py = Vec::with_capacity(n + 2);
+  px.push(2.0 * x[0] - x[1]);
+  py.push(2.0 * y[0] - y[1]);
+  px.extend_from_slice(x);
+  py.extend_from_slice(y);
+  px.push(2.0 * x[n - 1] - x[n - 2]);
+  py.push(2.0 * y[n - 1] - y[n - 2]);
+
+  // Knot sequence of the curve parameter
+  let alpha = parameterization.alpha();
+  let mut knots = vec![0.0; n + 2];
+  for k in 1..n + 2 {
+    let dist = (px[k] - px[k - 1]).hypot(py[k] - py[k - 1]);
+    knots[k] = knots[k - 1] + dist.powf(alpha);
+  }
+
+  // Tangent at waypoint k with respect to the curve parameter
+  let tangent = |k: usize| {
+    let (t0, t1, t2) = (knots[k - 1], knots[k], knots[k + 1]);
+    (py[k] - py[k - 1]) / (t1 - t0) - (py[k + 1] - py[k - 1]) / (t2 - t0)
+      + (py[k + 1] - py[k]) / (t2 - t1)
+  };
+
+  let mut out = Function::new_delay(x[0]);
+  for k in 1..n {
+    let dur = px[k + 1] - px[k];
+    // Slopes with respect to x, the parameter is spread linearly over the interval
+    let scale = (1.0 - tension) * (knots[k + 1] - knots[k]) / dur;
+    let start = (py[k], tangent(k) * scale);
+    let end = (py[k + 1], tangent(k + 1) * scale);
+    out.add_subfunction(Box::new(hermite::cubic(dur, start, end)));
+  }
+  Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::interface::FunctionOutput;
+
+  #[test]
+  fn waypoints() {
+    let x = vec![1.0, 2.0, 4.0, 5.0, 7.0];
+    let y = vec![0.0, 3.0, -1.0, 2.0, 2.0];
+    for p in [Parameterization::Uniform, Parameterization::Centripetal, Parameterization::Chordal].iter() {
+      let a = catmull_rom(&x, &y, *p).unwrap();
+      assert_eq!((1.0, 7.0), a.get_limits());
+      for (xk, yk) in x.iter().zip(y.iter()).take(4) {
+        assert!((a.generate(*xk).unwrap() - yk).abs() < 1e-12);
+      }
+    }
+  }
+  #[test]
+  fn uniform_tangent() {
+    // Classic Catmull-Rom uses (P2 - P0) / 2 per unit of parameter
+    let a = catmull_rom(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0], Parameterization::Uniform).unwrap();
+    let slope = (a.generate(1.0 + 1e-7).unwrap() - a.generate(1.0).unwrap()) / 1e-7;
+    assert!((slope - 2.0).abs() < 1e-5);
+  }
+  #[test]
+  fn line() {
+    // Evenly spaced waypoints on a line stay on the line for every parameterization
+    let x = vec![0.0, 1.0, 2.0, 3.0];
+    let y = vec![1.0, 3.0, 5.0, 7.0];
+    for p in [Parameterization::Uniform, Parameterization::Centripetal, Parameterization::Chordal].iter() {
+      let a = catmull_rom(&x, &y, *p).unwrap();
+      for step in 0..30 {
+        let t = step as f64 / 10.0;
+        assert!((a.generate(t).unwrap() - (1.0 + 2.0 * t)).abs() < 1e-12);
+      }
+    }
+  }
+  #[test]
+  fn full_tension() {
+    // Full tension stops at every waypoint
+    let a = cardinal(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0], 1.0, Parameterization::Uniform).unwrap();
+    let slope = (a.generate(1.0 + 1e-7).unwrap() - a.generate(1.0).unwrap()) / 1e-7;
+    assert!(slope.abs() < 1e-5);
+  }
+  #[test]
+  fn unsorted() {
+    let a = catmull_rom(&[0.0, 2.0, 1.0], &[0.0, 1.0, 2.0], Parameterization::Uniform);
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+  }
+}
@@ -0,0 +1,30 @@
+use std::fmt;
+
+/// Errors reported while building functions
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+  /// A rational function has a pole at this value of its interval
+  Pole(f64),
+  /// A linear system that had to be solved was singular
+  Singular,
+  /// A function that must be monotonic changes direction or is flat at this x
+  NotMonotonic(f64),
+  /// An input does not meet the requirement that is described
+  InvalidInput(&'static str),
+}
+
+impl fmt::Display for Error {
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter<'_>,
+  ) -> fmt::Result {
+    match self {
+      Error::Pole(x) => write!(f, "denominator has a pole at {} inside of the interval", x),
+      Error::Singular => write!(f, "linear system is singular"),
+      Error::NotMonotonic(x) => write!(f, "function is not strictly monotonic at {}", x),
+      Error::InvalidInput(requirement) => write!(f, "invalid input, {}", requirement),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
@@ -1,12 +1,58 @@
 /// Definition of subfunction interface
 pub mod interface;
 
+/// Affine map shared by subfunctions defined over an interval
+mod mapping;
+
 /// Definition of polynomial
 pub mod polynomial;
 
 /// Definition of bump
 pub mod bump;
 
+/// Definition of closure
+pub mod closure;
+
+/// Definition of Chebyshev series
+pub mod chebyshev;
+
+/// Definition of Legendre series
+pub mod legendre;
+
+/// Definition of Bézier curves
+pub mod bezier;
+
+/// Definition of PCHIP and Akima knot interpolants
+pub mod interpolant;
+
+/// Definition of B-splines and NURBS
+pub mod bspline;
+
+/// Definition of Hermite segments
+pub mod hermite;
+
+/// Definition of constant, hold, step and ramp primitives
+pub mod primitive;
+
+/// Definition of rational functions
+pub mod rational;
+
+/// Definition of Fourier series
+pub mod fourier;
+
+/// Definition of seeded noise
+pub mod noise;
+
+/// Definition of second-order system responses
+pub mod second_order;
+
+/// Definition of smoothstep and easing curves
+pub mod easing;
+
+/// Definition of tables of evenly spaced samples
+pub mod table;
+
+use crate::error::Error;
 use interface::FunctionOutput;
 
 /// Factory to generate all subfunctions
@@ -21,8 +67,176 @@ impl Factory {
     coeff: Vec<f64>,
     reverse: bool,
   ) -> Box<dyn FunctionOutput> {
-    let new = Box::new(polynomial::Polynomial::new(dur, interval, coeff, reverse));
-    new
+    Box::new(polynomial::Polynomial::new(dur, interval, coeff, reverse))
+  }
+  /// Generates a polynomial evaluated with compensated Horner
+  pub fn polynomial_compensated(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    coeff: Vec<f64>,
+    reverse: bool,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(polynomial::Polynomial::new_compensated(dur, interval, coeff, reverse))
+  }
+  /// Generates a rational function, it is an error for the denominator to have a pole in the interval
+  pub fn rational(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    numerator: Vec<f64>,
+    denominator: Vec<f64>,
+    reverse: bool,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = rational::Rational::new(dur, interval, numerator, denominator, reverse)?;
+    Ok(Box::new(new))
+  }
+  /// Generates the [m/n] Padé approximant of a Taylor series
+  pub fn pade(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    taylor: &[f64],
+    m: usize,
+    n: usize,
+    reverse: bool,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = rational::Rational::pade(dur, interval, taylor, m, n, reverse)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a Fourier series from cosine (a_n) and sine (b_n) harmonic coefficients
+  pub fn fourier(
+    &self,
+    dur: f64,
+    frequency: f64,
+    offset: f64,
+    cosine: Vec<f64>,
+    sine: Vec<f64>,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(fourier::Fourier::new(dur, frequency, offset, cosine, sine))
+  }
+  /// Generates a Fourier series from harmonic magnitudes and phases, it is an error for their lengths to differ
+  pub fn fourier_polar(
+    &self,
+    dur: f64,
+    frequency: f64,
+    offset: f64,
+    magnitude: Vec<f64>,
+    phase: Vec<f64>,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = fourier::Fourier::polar(dur, frequency, offset, magnitude, phase)?;
+    Ok(Box::new(new))
+  }
+  /// Generates seeded noise that is a pure function of x and the seed
+  pub fn noise(
+    &self,
+    dur: f64,
+    kind: noise::Kind,
+    rate: f64,
+    scale: f64,
+    seed: u64,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(noise::Noise::new(dur, kind, rate, scale, seed))
+  }
+  /// Generates a second-order response from its initial (value, slope), it is an error for the frequency not to be positive or the damping negative
+  pub fn second_order(
+    &self,
+    dur: f64,
+    natural_frequency: f64,
+    damping: f64,
+    gain: f64,
+    initial: (f64, f64),
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = second_order::SecondOrder::new(dur, natural_frequency, damping, gain, initial)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a constant
+  pub fn constant(
+    &self,
+    dur: f64,
+    value: f64,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(primitive::constant(dur, value))
+  }
+  /// Generates a constant that holds the last value of another subfunction
+  pub fn hold(
+    &self,
+    dur: f64,
+    previous: &dyn FunctionOutput,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(primitive::hold(dur, previous))
+  }
+  /// Generates a step from low to high at the edge with the given value at the edge
+  pub fn step(
+    &self,
+    dur: f64,
+    edge: f64,
+    values: (f64, f64),
+    at_edge: f64,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(primitive::Step::new(dur, edge, values, at_edge))
+  }
+  /// Generates a ramp from the start value to the end value
+  pub fn ramp(
+    &self,
+    dur: f64,
+    values: (f64, f64),
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(primitive::ramp(dur, values))
+  }
+  /// Generates a ramp that holds its values outside of it, it is an error for the ramp to end before it begins
+  pub fn saturating_ramp(
+    &self,
+    dur: f64,
+    ramp: (f64, f64),
+    values: (f64, f64),
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = primitive::SaturatingRamp::new(dur, ramp, values)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a cubic Hermite segment from (value, slope) at each end
+  pub fn hermite_cubic(
+    &self,
+    dur: f64,
+    start: (f64, f64),
+    end: (f64, f64),
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(hermite::cubic(dur, start, end))
+  }
+  /// Generates a quintic Hermite segment from (value, slope, curvature) at each end
+  pub fn hermite_quintic(
+    &self,
+    dur: f64,
+    start: (f64, f64, f64),
+    end: (f64, f64, f64),
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(hermite::quintic(dur, start, end))
+  }
+  /// Generates Perlin's smoothstep from start to end value
+  pub fn smoothstep(
+    &self,
+    dur: f64,
+    values: (f64, f64),
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(easing::smoothstep(dur, values))
+  }
+  /// Generates Perlin's smootherstep from start to end value
+  pub fn smootherstep(
+    &self,
+    dur: f64,
+    values: (f64, f64),
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(easing::smootherstep(dur, values))
+  }
+  /// Generates an easing curve from start to end value
+  pub fn easing(
+    &self,
+    dur: f64,
+    family: easing::Family,
+    mode: easing::Mode,
+    values: (f64, f64),
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(easing::Easing::new(dur, family, mode, values))
   }
   /// Generates a bump
   pub fn bump(
@@ -32,8 +246,124 @@ impl Factory {
     scale: f64,
     offset: f64,
   ) -> Box<dyn FunctionOutput> {
-    let new = Box::new(bump::Bump::new(dur, interval, scale, offset));
-    new
+    Box::new(bump::Bump::new(dur, interval, scale, offset))
+  }
+  /// Generates a polynomial with coefficients in the Chebyshev basis
+  pub fn chebyshev(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    coeff: Vec<f64>,
+    reverse: bool,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(chebyshev::Chebyshev::new(dur, interval, coeff, reverse))
+  }
+  /// Generates a polynomial with coefficients in the Legendre basis
+  pub fn legendre(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    coeff: Vec<f64>,
+    reverse: bool,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(legendre::Legendre::new(dur, interval, coeff, reverse))
+  }
+  /// Generates a monotone piecewise cubic (PCHIP) interpolant, it is an error for the knots not to describe a function
+  pub fn pchip(
+    &self,
+    dur: f64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = interpolant::Interpolant::pchip(dur, x, y)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a modified Akima (makima) interpolant, it is an error for the knots not to describe a function
+  pub fn akima(
+    &self,
+    dur: f64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = interpolant::Interpolant::akima(dur, x, y)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a B-spline, it is an error for the knots not to match the degree and control points
+  pub fn bspline(
+    &self,
+    dur: f64,
+    degree: usize,
+    knots: Vec<f64>,
+    control: Vec<f64>,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = bspline::BSpline::new(dur, degree, knots, control)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a NURBS, it is an error for the knots or weights not to match the control points
+  pub fn nurbs(
+    &self,
+    dur: f64,
+    degree: usize,
+    knots: Vec<f64>,
+    control: Vec<f64>,
+    weights: Vec<f64>,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = bspline::Nurbs::new(dur, degree, knots, control, weights)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a Bézier curve from its control point values
+  pub fn bezier(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    control: Vec<f64>,
+  ) -> Box<dyn FunctionOutput> {
+    Box::new(bezier::Bezier::new(dur, interval, control))
+  }
+  /// Generates a CSS style cubic-bezier(x1,y1,x2,y2) easing, it is an error for x1 or x2 to be outside of [0,1]
+  pub fn cubic_bezier(
+    &self,
+    dur: f64,
+    p1: (f64, f64),
+    p2: (f64, f64),
+    values: (f64, f64),
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = bezier::CubicBezier::new(dur, p1, p2, values)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a table of evenly spaced samples, it is an error for it to be empty or the rate not positive and finite
+  pub fn table(
+    &self,
+    rate: f64,
+    values: Vec<f64>,
+    reconstruction: table::Reconstruction,
+  ) -> Result<Box<dyn FunctionOutput>, Error> {
+    let new = table::Table::new(rate, values, reconstruction)?;
+    Ok(Box::new(new))
+  }
+  /// Generates a subfunction from a user defined closure
+  pub fn closure<F>(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    f: F,
+  ) -> Box<dyn FunctionOutput>
+  where
+    F: Fn(f64) -> f64 + Send + Sync + 'static,
+  {
+    Box::new(closure::Closure::new(dur, interval, f))
+  }
+  /// Generates a subfunction from a user defined closure traversed from the end of its interval
+  pub fn closure_reversed<F>(
+    &self,
+    dur: f64,
+    interval: (f64, f64),
+    f: F,
+  ) -> Box<dyn FunctionOutput>
+  where
+    F: Fn(f64) -> f64 + Send + Sync + 'static,
+  {
+    Box::new(closure::Closure::new_reversed(dur, interval, f))
   }
 }
 
@@ -48,9 +378,179 @@ mod tests {
     assert_eq!(Some(6.5), a.generate(0.5));
   }
   #[test]
+  fn polynomial_compensated() {
+    let factory = Factory;
+    let a = factory.polynomial_compensated(1.0, (0.0, 1.0), vec![4.0, 5.0], false);
+    assert_eq!(Some(6.5), a.generate(0.5));
+  }
+  #[test]
+  fn rational() {
+    let factory = Factory;
+    let a = factory.rational(1.0, (0.0, 1.0), vec![4.0, 5.0], vec![1.0], false).unwrap();
+    assert_eq!(Some(6.5), a.generate(0.5));
+    assert!(factory.rational(1.0, (0.0, 1.0), vec![1.0], vec![-0.5, 1.0], false).is_err());
+  }
+  #[test]
+  fn pade() {
+    let factory = Factory;
+    let a = factory.pade(1.0, (0.0, 1.0), &[4.0, 5.0], 1, 0, false).unwrap();
+    assert_eq!(Some(6.5), a.generate(0.5));
+  }
+  #[test]
+  fn fourier() {
+    let factory = Factory;
+    let a = factory.fourier(1.0, 1.0, 4.0, vec![0.0], vec![1.0]);
+    assert_eq!(Some(4.0), a.generate(0.0));
+  }
+  #[test]
+  fn fourier_polar() {
+    let factory = Factory;
+    let a = factory.fourier_polar(1.0, 1.0, 4.0, vec![1.0], vec![0.0]).unwrap();
+    assert_eq!(Some(5.0), a.generate(0.0));
+  }
+  #[test]
+  fn noise() {
+    let factory = Factory;
+    let a = factory.noise(1.0, noise::Kind::Perlin, 10.0, 1.0, 0);
+    assert_eq!(Some(0.0), a.generate(0.5));
+  }
+  #[test]
+  fn second_order() {
+    let factory = Factory;
+    let a = factory.second_order(1.0, 2.0, 0.5, 3.0, (3.0, 0.0)).unwrap();
+    assert_eq!(Some(3.0), a.generate(0.5));
+  }
+  #[test]
+  fn constant() {
+    let factory = Factory;
+    let a = factory.constant(1.0, 4.0);
+    assert_eq!(Some(4.0), a.generate(0.5));
+  }
+  #[test]
+  fn hold() {
+    let factory = Factory;
+    let a = factory.constant(1.0, 4.0);
+    let b = factory.hold(1.0, a.as_ref());
+    assert_eq!(Some(4.0), b.generate(0.5));
+  }
+  #[test]
+  fn step() {
+    let factory = Factory;
+    let a = factory.step(1.0, 0.5, (4.0, 5.0), 5.0);
+    assert_eq!(Some(5.0), a.generate(0.5));
+  }
+  #[test]
+  fn ramp() {
+    let factory = Factory;
+    let a = factory.ramp(1.0, (4.0, 5.0));
+    assert_eq!(Some(4.5), a.generate(0.5));
+  }
+  #[test]
+  fn saturating_ramp() {
+    let factory = Factory;
+    let a = factory.saturating_ramp(1.0, (0.25, 0.75), (4.0, 5.0)).unwrap();
+    assert_eq!(Some(4.5), a.generate(0.5));
+  }
+  #[test]
+  fn hermite_cubic() {
+    let factory = Factory;
+    let a = factory.hermite_cubic(2.0, (4.0, 0.5), (5.0, 0.5));
+    assert_eq!(Some(4.5), a.generate(1.0));
+  }
+  #[test]
+  fn hermite_quintic() {
+    let factory = Factory;
+    let a = factory.hermite_quintic(2.0, (4.0, 0.5, 0.0), (5.0, 0.5, 0.0));
+    assert_eq!(Some(4.5), a.generate(1.0));
+  }
+  #[test]
+  fn smoothstep() {
+    let factory = Factory;
+    let a = factory.smoothstep(2.0, (4.0, 5.0));
+    assert_eq!(Some(4.5), a.generate(1.0));
+  }
+  #[test]
+  fn smootherstep() {
+    let factory = Factory;
+    let a = factory.smootherstep(2.0, (4.0, 5.0));
+    assert_eq!(Some(4.5), a.generate(1.0));
+  }
+  #[test]
+  fn easing() {
+    let factory = Factory;
+    let a = factory.easing(2.0, easing::Family::Cubic, easing::Mode::InOut, (4.0, 5.0));
+    assert_eq!(Some(4.5), a.generate(1.0));
+  }
+  #[test]
   fn bump() {
     let factory = Factory;
     let a = factory.bump(1.0, (-1.0, 1.0), 1.0, 0.0);
     assert_eq!(Some(1.0), a.generate(0.5));
   }
+  #[test]
+  fn chebyshev() {
+    let factory = Factory;
+    let a = factory.chebyshev(1.0, (-1.0, 1.0), vec![4.0, 5.0], false);
+    assert_eq!(Some(4.0), a.generate(0.5));
+  }
+  #[test]
+  fn legendre() {
+    let factory = Factory;
+    let a = factory.legendre(1.0, (-1.0, 1.0), vec![4.0, 5.0], false);
+    assert_eq!(Some(4.0), a.generate(0.5));
+  }
+  #[test]
+  fn pchip() {
+    let factory = Factory;
+    let a = factory.pchip(1.0, vec![0.0, 1.0], vec![4.0, 5.0]).unwrap();
+    assert_eq!(Some(4.5), a.generate(0.5));
+  }
+  #[test]
+  fn akima() {
+    let factory = Factory;
+    let a = factory.akima(1.0, vec![0.0, 1.0], vec![4.0, 5.0]).unwrap();
+    assert_eq!(Some(4.5), a.generate(0.5));
+  }
+  #[test]
+  fn bspline() {
+    let factory = Factory;
+    let a = factory.bspline(1.0, 1, vec![0.0, 0.0, 1.0, 1.0], vec![4.0, 5.0]).unwrap();
+    assert_eq!(Some(4.5), a.generate(0.5));
+  }
+  #[test]
+  fn nurbs() {
+    let factory = Factory;
+    let a = factory.nurbs(1.0, 1, vec![0.0, 0.0, 1.0, 1.0], vec![4.0, 5.0], vec![1.0, 1.0]).unwrap();
+    assert_eq!(Some(4.5), a.generate(0.5));
+  }
+  #[test]
+  fn bezier() {
+    let factory = Factory;
+    let a = factory.bezier(1.0, (0.0, 1.0), vec![4.0, 5.0]);
+    assert_eq!(Some(4.5), a.generate(0.5));
+  }
+  #[test]
+  fn cubic_bezier() {
+    let factory = Factory;
+    let a = factory.cubic_bezier(1.0, (0.42, 0.0), (0.58, 1.0), (0.0, 2.0)).unwrap();
+    assert_eq!(Some(0.0), a.generate(0.0));
+  }
+  #[test]
+  fn table() {
+    let factory = Factory;
+    let a = factory.table(2.0, vec![4.0, 5.0], table::Reconstruction::FirstOrder).unwrap();
+    assert_eq!(Some(4.5), a.generate(0.25));
+  }
+  #[test]
+  fn closure() {
+    let factory = Factory;
+    let a = factory.closure(1.0, (0.0, 2.0), |x| x * x);
+    assert_eq!(Some(1.0), a.generate(0.5));
+  }
+  #[test]
+  fn closure_reversed() {
+    let factory = Factory;
+    let a = factory.closure_reversed(1.0, (0.0, 2.0), |x| x * x);
+    assert_eq!(Some(4.0), a.generate(0.0));
+  }
 }
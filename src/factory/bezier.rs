@@ -0,0 +1,249 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use crate::factory::mapping::Mapping;
+
+/// Bézier curve of any degree
+///
+/// The Bézier curve requires three inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Interval: The part of the curve parameter [0,1] that is mapped onto the duration
+/// * Control: Control point values, the degree of the curve is one less than the count
+///
+/// The curve is evaluated with de Casteljau's algorithm, which only ever
+/// takes convex combinations of the control points so it is numerically
+/// stable for any degree.
+pub struct Bezier {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Map from the duration onto the curve parameter.
+  mapping: Mapping,
+  // Control point values, the first and last are the curve end points.
+  control: Vec<f64>,
+}
+
+/// Constructor for the Bezier
+impl Bezier {
+  /// Creates a new Bézier curve
+  pub fn new(
+    dur: f64,
+    interval: (f64, f64),
+    control: Vec<f64>,
+  ) -> Bezier {
+    Bezier {
+      duration: dur,
+      mapping: Mapping::new(dur, interval, false),
+      control,
+    }
+  }
+}
+
+/// Evaluates a Bézier curve at parameter t with de Casteljau's algorithm
+fn de_casteljau(
+  control: &[f64],
+  t: f64,
+) -> f64 {
+  let mut points = control.to_vec();
+  for level in (1..points.len()).rev() {
+    for idx in 0..level {
+      points[idx] = (1.0 - t) * points[idx] + t * points[idx + 1];
+    }
+  }
+  match points.first() {
+    None => 0.0,
+    Some(y) => *y,
+  }
+}
+
+impl FunctionOutput for Bezier {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      result = Some(de_casteljau(&self.control, self.mapping.apply(x)));
+    }
+    result
+  }
+}
+
+/// Cubic Bézier easing curve
+///
+/// This follows the CSS `cubic-bezier(x1,y1,x2,y2)` timing function. The
+/// curve runs from (0,0) to (1,1) with the two inner control points given by
+/// the user. The horizontal axis is progress through the duration so the
+/// curve parameter is solved from x before the vertical value is returned.
+/// The easing output is then scaled from the start value to the end value.
+pub struct CubicBezier {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // First inner control point (x1,y1)
+  p1: (f64, f64),
+  // Second inner control point (x2,y2)
+  p2: (f64, f64),
+  // Output values at the start and the end of the easing
+  values: (f64, f64),
+}
+
+/// Constructor for the CubicBezier
+impl CubicBezier {
+  /// Creates a new cubic Bézier easing
+  ///
+  /// Like CSS the x coordinates must be inside [0,1] so that the curve is a
+  /// function of progress, the y coordinates are free to overshoot. Returns
+  /// Error::InvalidInput when an x coordinate is outside of [0,1].
+  pub fn new(
+    dur: f64,
+    p1: (f64, f64),
+    p2: (f64, f64),
+    values: (f64, f64),
+  ) -> Result<CubicBezier, Error> {
+    if !(0.0..=1.0).contains(&p1.0) || !(0.0..=1.0).contains(&p2.0) {
+      return Err(Error::InvalidInput("cubic-bezier x coordinates must be inside [0,1]"));
+    }
+    Ok(CubicBezier {
+      duration: dur,
+      p1,
+      p2,
+      values,
+    })
+  }
+  /// Solves for the curve parameter that produces progress x
+  fn solve_t(
+    &self,
+    x: f64,
+  ) -> f64 {
+    let bx = [0.0, self.p1.0, self.p2.0, 1.0];
+    // Newton's method converges in a handful of steps for most curves
+    let mut t = x;
+    for _ in 0..8 {
+      let err = cubic(&bx, t) - x;
+      if err.abs() < 1e-12 {
+        return t;
+      }
+      let slope = cubic_slope(&bx, t);
+      if slope.abs() < 1e-9 {
+        break;
+      }
+      t -= err / slope;
+    }
+    // Fall back to bisection, x(t) is monotonic when x1,x2 are in [0,1]
+    let mut lo: f64 = 0.0;
+    let mut hi: f64 = 1.0;
+    t = x;
+    while hi - lo > 1e-12 {
+      if cubic(&bx, t) < x {
+        lo = t;
+      } else {
+        hi = t;
+      }
+      t = 0.5 * (lo + hi);
+    }
+    t
+  }
+}
+
+/// Evaluates a cubic Bézier in Bernstein form
+fn cubic(
+  p: &[f64; 4],
+  t: f64,
+) -> f64 {
+  let s = 1.0 - t;
+  s * s * s * p[0] + 3.0 * s * s * t * p[1] + 3.0 * s * t * t * p[2] + t * t * t * p[3]
+}
+
+/// Evaluates the derivative of a cubic Bézier with respect to t
+fn cubic_slope(
+  p: &[f64; 4],
+  t: f64,
+) -> f64 {
+  let s = 1.0 - t;
+  3.0 * s * s * (p[1] - p[0]) + 6.0 * s * t * (p[2] - p[1]) + 3.0 * t * t * (p[3] - p[2])
+}
+
+impl FunctionOutput for CubicBezier {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let t = self.solve_t(x / self.duration);
+      let ease = cubic(&[0.0, self.p1.1, self.p2.1, 1.0], t);
+      result = Some(self.values.0 + ease * (self.values.1 - self.values.0));
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bezier() {
+    // Quadratic with control points 0,1,0 peaks at 0.5 half way through
+    let a = Bezier::new(2.0, (0.0, 1.0), vec![0.0, 1.0, 0.0]);
+    assert_eq!(Some(0.0), a.generate(0.0));
+    assert_eq!(Some(0.5), a.generate(1.0));
+    assert_eq!(Some(0.375), a.generate(0.5));
+  }
+  #[test]
+  fn bezier_linear() {
+    let a = Bezier::new(1.0, (0.0, 1.0), vec![2.0, 4.0]);
+    assert_eq!(Some(3.0), a.generate(0.5));
+  }
+  #[test]
+  fn bezier_interval() {
+    // Second half of the same quadratic
+    let a = Bezier::new(1.0, (0.5, 1.0), vec![0.0, 1.0, 0.0]);
+    assert_eq!(Some(0.5), a.generate(0.0));
+    assert_eq!(Some(0.375), a.generate(0.5));
+  }
+  #[test]
+  fn cubic_bezier_linear() {
+    // cubic-bezier(0,0,1,1) is the identity
+    let a = CubicBezier::new(1.0, (0.0, 0.0), (1.0, 1.0), (0.0, 1.0)).unwrap();
+    for step in 0..10 {
+      let x = step as f64 / 10.0;
+      assert!((a.generate(x).unwrap() - x).abs() < 1e-9);
+    }
+  }
+  #[test]
+  fn cubic_bezier_ease() {
+    // CSS "ease" is cubic-bezier(0.25,0.1,0.25,1.0), its value at half way
+    // through is roughly 0.8024
+    let a = CubicBezier::new(1.0, (0.25, 0.1), (0.25, 1.0), (0.0, 1.0)).unwrap();
+    assert_eq!(0.8024, (a.generate(0.5).unwrap() * 10000.0).round() / 10000.0);
+  }
+  #[test]
+  fn cubic_bezier_values() {
+    let a = CubicBezier::new(2.0, (0.42, 0.0), (0.58, 1.0), (10.0, 20.0)).unwrap();
+    assert_eq!(Some(10.0), a.generate(0.0));
+    // ease-in-out is symmetric
+    assert!((a.generate(1.0).unwrap() - 15.0).abs() < 1e-9);
+  }
+  #[test]
+  fn cubic_bezier_invalid() {
+    let a = CubicBezier::new(1.0, (-0.5, 0.0), (0.5, 1.0), (0.0, 1.0));
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Bezier::new(1.0, (0.0, 1.0), vec![0.0, 1.0]);
+    let b = CubicBezier::new(1.0, (0.0, 0.0), (1.0, 1.0), (0.0, 1.0)).unwrap();
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+    assert_eq!(None, b.generate(-0.1));
+    assert_eq!(None, b.generate(1.0));
+  }
+}
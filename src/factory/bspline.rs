@@ -0,0 +1,362 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::{FunctionOutput, Piece};
+use crate::factory::mapping::Mapping;
+use crate::factory::polynomial::Polynomial;
+use crate::function::Function;
+
+/// B-spline of any degree
+///
+/// The B-spline requires four inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Degree: Polynomial degree of every span of the spline
+/// * Knots: Non-decreasing knot vector with (control count + degree + 1) entries
+/// * Control: Control point values
+///
+/// The valid parameter range of the spline is [knots[degree], knots[count])
+/// and it is mapped onto the duration. A clamped knot vector (degree + 1 equal
+/// knots on each end) makes the spline start and end on the first and last
+/// control point, an unclamped knot vector does not. The spline is evaluated
+/// with de Boor's algorithm.
+pub struct BSpline {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Map from the duration onto the valid parameter range
+  mapping: Mapping,
+  // Polynomial degree of each span
+  degree: usize,
+  // Knot vector
+  knots: Vec<f64>,
+  // Control point values, stored as one dimensional points for de Boor
+  control: Vec<[f64; 1]>,
+}
+
+/// Constructors for the BSpline
+impl BSpline {
+  /// Creates a new B-spline from an explicit knot vector
+  ///
+  /// Returns Error::InvalidInput when the knot vector does not match the
+  /// degree and the number of control points.
+  pub fn new(
+    dur: f64,
+    degree: usize,
+    knots: Vec<f64>,
+    control: Vec<f64>,
+  ) -> Result<BSpline, Error> {
+    check_knots(degree, &knots, control.len())?;
+    Ok(BSpline {
+      duration: dur,
+      mapping: Mapping::new(dur, domain(degree, &knots), false),
+      degree,
+      knots,
+      control: control.iter().map(|c| [*c]).collect(),
+    })
+  }
+  /// Creates a new B-spline with a uniform clamped knot vector on [0,1]
+  ///
+  /// Returns Error::InvalidInput when there are not more control points than
+  /// the degree.
+  pub fn clamped(
+    dur: f64,
+    degree: usize,
+    control: Vec<f64>,
+  ) -> Result<BSpline, Error> {
+    if control.len() <= degree {
+      return Err(Error::InvalidInput("B-spline needs more control points than its degree"));
+    }
+    let spans = control.len() - degree;
+    let mut knots = vec![0.0; degree];
+    knots.extend((0..=spans).map(|idx| idx as f64 / spans as f64));
+    knots.extend(vec![1.0; degree]);
+    BSpline::new(dur, degree, knots, control)
+  }
+  /// Splits the B-spline into equivalent polynomial subfunctions
+  ///
+  /// Every non-empty knot span becomes one Polynomial over the interval
+  /// (0,1) whose duration is that span's share of the overall duration.
+  /// Chaining them in a Function reproduces the spline exactly.
+  pub fn to_function(&self) -> Function {
+    let (start, stop) = domain(self.degree, &self.knots);
+    let mut out = Function::new();
+    for k in self.degree..self.knots.len() - self.degree - 1 {
+      let h = self.knots[k + 1] - self.knots[k];
+      if h <= 0.0 {
+        continue;
+      }
+      let coeff = self.span_polynomial(k);
+      let dur = h / (stop - start) * self.duration;
+      out.add_subfunction(Box::new(Polynomial::new(dur, (0.0, 1.0), coeff, false)));
+    }
+    out
+  }
+  /// Power-basis coefficients of knot span k in the local variable s in [0,1]
+  ///
+  /// This is de Boor's algorithm carried out on polynomials instead of
+  /// numbers. Each blending factor is linear in s so every level raises the
+  /// degree by one.
+  fn span_polynomial(
+    &self,
+    k: usize,
+  ) -> Vec<f64> {
+    let p = self.degree;
+    let t = &self.knots;
+    let h = t[k + 1] - t[k];
+    let mut d: Vec<Vec<f64>> = (0..=p).map(|j| self.control[j + k - p].to_vec()).collect();
+    for r in 1..=p {
+      for j in (r..=p).rev() {
+        let left = t[j + k - p];
+        let width = t[j + 1 + k - r] - left;
+        // alpha(s) = (t_k + s*h - left) / width
+        let alpha = [(t[k] - left) / width, h / width];
+        let mut next = vec![0.0; r + 1];
+        for (idx, c) in d[j - 1].iter().enumerate() {
+          next[idx] += alpha[0].mul_add(-c, *c);
+          next[idx + 1] -= alpha[1] * c;
+        }
+        for (idx, c) in d[j].iter().enumerate() {
+          next[idx] += alpha[0] * c;
+          next[idx + 1] += alpha[1] * c;
+        }
+        d[j] = next;
+      }
+    }
+    d.swap_remove(p)
+  }
+}
+
+/// Makes sure the knot vector matches the degree and control points
+fn check_knots(
+  degree: usize,
+  knots: &[f64],
+  count: usize,
+) -> Result<(), Error> {
+  if count <= degree {
+    return Err(Error::InvalidInput("B-spline needs more control points than its degree"));
+  }
+  if knots.len() != count + degree + 1 {
+    return Err(Error::InvalidInput("B-spline knot vector must have control count + degree + 1 entries"));
+  }
+  if !knots.windows(2).all(|w| w[1] >= w[0]) {
+    return Err(Error::InvalidInput("B-spline knots must be non-decreasing"));
+  }
+  if knots[count] <= knots[degree] {
+    return Err(Error::InvalidInput("B-spline valid parameter range must not be empty"));
+  }
+  Ok(())
+}
+
+/// Valid parameter range of a B-spline
+fn domain(
+  degree: usize,
+  knots: &[f64],
+) -> (f64, f64) {
+  (knots[degree], knots[knots.len() - degree - 1])
+}
+
+/// Index of the knot span that contains u
+fn find_span(
+  degree: usize,
+  knots: &[f64],
+  u: f64,
+) -> usize {
+  let last = knots.len() - degree - 2;
+  (knots.partition_point(|knot| *knot <= u).max(1) - 1).clamp(degree, last)
+}
+
+/// Evaluates a spline at u with de Boor's algorithm
+///
+/// Works on any vector space so NURBS can reuse it on homogeneous points.
+fn de_boor<const N: usize>(
+  degree: usize,
+  knots: &[f64],
+  control: &[[f64; N]],
+  u: f64,
+) -> [f64; N] {
+  let p = degree;
+  let k = find_span(degree, knots, u);
+  let mut d: Vec<[f64; N]> = (0..=p).map(|j| control[j + k - p]).collect();
+  for r in 1..=p {
+    for j in (r..=p).rev() {
+      let left = knots[j + k - p];
+      let alpha = (u - left) / (knots[j + 1 + k - r] - left);
+      let prev = d[j - 1];
+      for (n, value) in d[j].iter_mut().enumerate() {
+        *value = (1.0 - alpha) * prev[n] + alpha * *value;
+      }
+    }
+  }
+  d[p]
+}
+
+impl FunctionOutput for BSpline {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      result = Some(de_boor(self.degree, &self.knots, &self.control, self.mapping.apply(x))[0]);
+    }
+    result
+  }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    self.to_function().pieces()
+  }
+}
+
+/// Non-uniform rational B-spline
+///
+/// NURBS behave like a BSpline except every control point also has a weight
+/// that pulls the curve towards it. With equal weights the NURBS is the same
+/// as the BSpline. The rational curve is evaluated with de Boor's algorithm
+/// on the weighted (homogeneous) control points.
+pub struct Nurbs {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Map from the duration onto the valid parameter range
+  mapping: Mapping,
+  // Polynomial degree of each span
+  degree: usize,
+  // Knot vector
+  knots: Vec<f64>,
+  // Homogeneous control points (weight * value, weight)
+  control: Vec<[f64; 2]>,
+}
+
+/// Constructor for the Nurbs
+impl Nurbs {
+  /// Creates a new NURBS from an explicit knot vector and weights
+  ///
+  /// Returns Error::InvalidInput when the knot vector does not match the
+  /// degree and the number of control points, or when there is not one
+  /// positive weight per control point.
+  pub fn new(
+    dur: f64,
+    degree: usize,
+    knots: Vec<f64>,
+    control: Vec<f64>,
+    weights: Vec<f64>,
+  ) -> Result<Nurbs, Error> {
+    check_knots(degree, &knots, control.len())?;
+    if control.len() != weights.len() {
+      return Err(Error::InvalidInput("NURBS needs one weight per control point"));
+    }
+    if !weights.iter().all(|w| *w > 0.0) {
+      return Err(Error::InvalidInput("NURBS weights must be positive"));
+    }
+    Ok(Nurbs {
+      duration: dur,
+      mapping: Mapping::new(dur, domain(degree, &knots), false),
+      degree,
+      knots,
+      control: control.iter().zip(weights.iter()).map(|(c, w)| [c * w, *w]).collect(),
+    })
+  }
+}
+
+impl FunctionOutput for Nurbs {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let point = de_boor(self.degree, &self.knots, &self.control, self.mapping.apply(x));
+      result = Some(point[0] / point[1]);
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn clamped() {
+    // A clamped spline starts on the first control point
+    let a = BSpline::clamped(1.0, 3, vec![1.0, 4.0, -2.0, 0.5, 3.0]).unwrap();
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert!((a.generate(1.0 - 1e-12).unwrap() - 3.0).abs() < 1e-9);
+  }
+  #[test]
+  fn bernstein() {
+    // With a single span the clamped spline is the Bézier curve
+    let a = BSpline::clamped(1.0, 2, vec![0.0, 1.0, 0.0]).unwrap();
+    assert_eq!(Some(0.5), a.generate(0.5));
+    assert_eq!(Some(0.375), a.generate(0.25));
+  }
+  #[test]
+  fn unclamped() {
+    // Uniform quadratic spline, the spans blend neighbouring control points
+    let knots = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let a = BSpline::new(2.0, 2, knots, vec![0.0, 2.0, 4.0, 2.0]).unwrap();
+    // Valid range is [2,4), it starts half way between the first two points
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(3.0), a.generate(1.0));
+  }
+  #[test]
+  fn to_function() {
+    let knots = vec![0.0, 0.0, 0.0, 0.0, 0.2, 0.7, 1.0, 1.0, 1.0, 1.0];
+    let a = BSpline::new(2.0, 3, knots, vec![1.0, 4.0, -2.0, 0.5, 3.0, 1.0]).unwrap();
+    let b = a.to_function();
+    assert_eq!(a.get_limits(), b.get_limits());
+    for step in 0..200 {
+      let x = step as f64 / 100.0;
+      assert!((a.generate(x).unwrap() - b.generate(x).unwrap()).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn repeated_interior_knot() {
+    // The empty span is skipped when converting to polynomials
+    let knots = vec![0.0, 0.0, 0.0, 0.5, 0.5, 1.0, 1.0, 1.0];
+    let a = BSpline::new(1.0, 2, knots, vec![0.0, 1.0, 2.0, 1.0, 0.0]).unwrap();
+    let b = a.to_function();
+    for step in 0..100 {
+      let x = step as f64 / 100.0;
+      assert!((a.generate(x).unwrap() - b.generate(x).unwrap()).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn nurbs_equal_weights() {
+    let knots = vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0];
+    let a = BSpline::new(1.0, 2, knots.clone(), vec![0.0, 3.0, -1.0, 2.0]).unwrap();
+    let b = Nurbs::new(1.0, 2, knots, vec![0.0, 3.0, -1.0, 2.0], vec![2.0; 4]).unwrap();
+    for step in 0..100 {
+      let x = step as f64 / 100.0;
+      assert!((a.generate(x).unwrap() - b.generate(x).unwrap()).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn nurbs_weight() {
+    // Quadratic x^2-like arc with a heavy middle point pulled up towards it
+    let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+    let a = Nurbs::new(1.0, 2, knots.clone(), vec![0.0, 1.0, 0.0], vec![1.0, 1.0, 1.0]).unwrap();
+    let b = Nurbs::new(1.0, 2, knots, vec![0.0, 1.0, 0.0], vec![1.0, 3.0, 1.0]).unwrap();
+    assert_eq!(Some(0.5), a.generate(0.5));
+    assert_eq!(Some(0.75), b.generate(0.5));
+  }
+  #[test]
+  fn bad_knots() {
+    let a = BSpline::new(1.0, 2, vec![0.0, 0.0, 1.0, 1.0], vec![0.0, 1.0, 2.0]);
+    let b = BSpline::clamped(1.0, 3, vec![0.0, 1.0, 2.0]);
+    let c = Nurbs::new(1.0, 1, vec![0.0, 0.0, 1.0, 1.0], vec![0.0, 1.0], vec![1.0, 0.0]);
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(b.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(c.err(), Some(Error::InvalidInput(_))));
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = BSpline::clamped(1.0, 2, vec![0.0, 1.0, 0.0]).unwrap();
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+}
@@ -29,13 +29,12 @@ impl Bump {
     scale: f64,
     offset: f64,
   ) -> Bump {
-    let out = Bump {
+    Bump {
       duration: dur,
-      interval: interval,
-      scale: scale,
-      offset: offset,
-    };
-    out
+      interval,
+      scale,
+      offset,
+    }
   }
 }
 
@@ -0,0 +1,110 @@
+// Make the standard subfunction interface available
+use crate::factory::interface::FunctionOutput;
+use crate::factory::mapping::Mapping;
+
+/// Closure wraps a user defined function
+///
+/// This is meant for one-off shapes that are not worth a new subfunction
+/// type. The closure requires three inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Interval: The interval of the closure input that is mapped onto the duration
+/// * Function: Any `Fn(f64) -> f64` that is evaluated over the interval
+pub struct Closure {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Map from the duration onto the interval of the closure input.
+  mapping: Mapping,
+  // User supplied function that is evaluated over the interval
+  function: Box<dyn Fn(f64) -> f64 + Send + Sync>,
+}
+
+/// Constructor for the Closure
+impl Closure {
+  /// Creates a new closure function
+  pub fn new<F>(
+    dur: f64,
+    interval: (f64, f64),
+    function: F,
+  ) -> Closure
+  where
+    F: Fn(f64) -> f64 + Send + Sync + 'static,
+  {
+    Closure {
+      duration: dur,
+      mapping: Mapping::new(dur, interval, false),
+      function: Box::new(function),
+    }
+  }
+  /// Creates a new closure function that traverses the interval backwards
+  ///
+  /// The start of the duration is the end of the interval, like a reversed
+  /// Polynomial.
+  pub fn new_reversed<F>(
+    dur: f64,
+    interval: (f64, f64),
+    function: F,
+  ) -> Closure
+  where
+    F: Fn(f64) -> f64 + Send + Sync + 'static,
+  {
+    Closure {
+      duration: dur,
+      mapping: Mapping::new(dur, interval, true),
+      function: Box::new(function),
+    }
+  }
+}
+
+impl FunctionOutput for Closure {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      result = Some((self.function)(self.mapping.apply(x)));
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn closure() {
+    let a = Closure::new(2.0, (0.0, 1.0), |x| 3.0 * x + 1.0);
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(2.5), a.generate(1.0));
+  }
+  #[test]
+  fn interval() {
+    let a = Closure::new(1.0, (-1.0, 1.0), |x| x.abs());
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(0.0), a.generate(0.5));
+    assert_eq!(Some(0.5), a.generate(0.75));
+  }
+  #[test]
+  fn reverse() {
+    let a = Closure::new_reversed(2.0, (0.0, 1.0), |x| 3.0 * x + 1.0);
+    assert_eq!(Some(4.0), a.generate(0.0));
+    assert_eq!(Some(2.5), a.generate(1.0));
+  }
+  #[test]
+  fn captured_state() {
+    let gain = 4.0;
+    let a = Closure::new(1.0, (0.0, 1.0), move |x| gain * x);
+    assert_eq!(Some(2.0), a.generate(0.5));
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Closure::new(1.0, (0.0, 1.0), |x| x);
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+}
@@ -0,0 +1,250 @@
+// Make the standard subfunction interface available
+use crate::factory::interface::FunctionOutput;
+use crate::factory::polynomial::Polynomial;
+use std::f64::consts::PI;
+
+/// Overshoot of the back easing, about 10% past the end value
+const BACK: f64 = 1.70158;
+
+/// Family of the easing curve
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Family {
+  /// t^2
+  Quad,
+  /// t^3
+  Cubic,
+  /// t^4
+  Quart,
+  /// Quarter of a cosine
+  Sine,
+  /// Exponential, normalized to start at 0 and end at 1
+  Expo,
+  /// Quarter of a circle
+  Circ,
+  /// Pulls back before moving towards the end value
+  Back,
+  /// Spring that rings with growing amplitude, normalized to start at 0
+  Elastic,
+  /// Ball bouncing onto the end value, mirrored for the in mode
+  Bounce,
+}
+
+/// Which end of the motion the easing is applied to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+  /// Starts slowly
+  In,
+  /// Ends slowly, the in curve rotated by half a turn
+  Out,
+  /// In curve over the first half and out curve over the second half
+  InOut,
+}
+
+/// Creates Perlin's smoothstep from the start value to the end value
+///
+/// 3t^2 - 2t^3 has zero slope at both ends. It is an ordinary Polynomial over
+/// the interval (0,1).
+pub fn smoothstep(
+  dur: f64,
+  values: (f64, f64),
+) -> Polynomial {
+  let rise = values.1 - values.0;
+  Polynomial::new(dur, (0.0, 1.0), vec![values.0, 0.0, 3.0 * rise, -2.0 * rise], false)
+}
+
+/// Creates Perlin's smootherstep from the start value to the end value
+///
+/// 6t^5 - 15t^4 + 10t^3 has zero slope and curvature at both ends. It is an
+/// ordinary Polynomial over the interval (0,1).
+pub fn smootherstep(
+  dur: f64,
+  values: (f64, f64),
+) -> Polynomial {
+  let rise = values.1 - values.0;
+  Polynomial::new(dur, (0.0, 1.0), vec![values.0, 0.0, 0.0, 10.0 * rise, -15.0 * rise, 6.0 * rise], false)
+}
+
+/// Easing curve
+///
+/// The easing requires four inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Family: Shape of the curve
+/// * Mode: Which end of the motion is eased
+/// * Values: (start, end) values
+///
+/// Every curve maps [0,1] onto [0,1], only back and elastic leave that range
+/// on the way, and is scaled to go from the start value to the end value
+/// over the duration. The out curve is 1 - in(1 - t) and the in-out curve
+/// runs the in curve at double speed for the first half and the out curve for
+/// the second half, so for back and elastic it differs slightly from the
+/// easings.net in-out curves which retune their constants.
+pub struct Easing {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Shape of the curve
+  family: Family,
+  // Which end of the motion is eased
+  mode: Mode,
+  // Start and end values
+  values: (f64, f64),
+}
+
+/// Constructor for the Easing
+impl Easing {
+  /// Creates a new easing curve
+  pub fn new(
+    dur: f64,
+    family: Family,
+    mode: Mode,
+    values: (f64, f64),
+  ) -> Easing {
+    Easing {
+      duration: dur,
+      family,
+      mode,
+      values,
+    }
+  }
+}
+
+/// In curve of every family
+fn ease_in(
+  family: Family,
+  t: f64,
+) -> f64 {
+  match family {
+    Family::Quad => t * t,
+    Family::Cubic => t * t * t,
+    Family::Quart => t * t * t * t,
+    Family::Sine => 1.0 - (0.5 * PI * t).cos(),
+    Family::Expo => ((10.0 * t).exp2() - 1.0) / 1023.0,
+    Family::Circ => 1.0 - (1.0 - t * t).max(0.0).sqrt(),
+    Family::Back => t * t * ((BACK + 1.0) * t - BACK),
+    // Penner's curve starts 2^-11 off of zero, that error is ramped out
+    Family::Elastic => elastic(t) - (1.0 - t) * elastic(0.0),
+    Family::Bounce => 1.0 - bounce_out(1.0 - t),
+  }
+}
+
+/// Robert Penner's elastic, a growing sine under an exponential envelope
+fn elastic(t: f64) -> f64 {
+  -(10.0 * t - 10.0).exp2() * ((10.0 * t - 10.75) * 2.0 * PI / 3.0).sin()
+}
+
+/// Robert Penner's bounce, four parabolas of shrinking height
+fn bounce_out(t: f64) -> f64 {
+  let (n, d) = (7.5625, 2.75);
+  if t < 1.0 / d {
+    n * t * t
+  } else if t < 2.0 / d {
+    let t = t - 1.5 / d;
+    n * t * t + 0.75
+  } else if t < 2.5 / d {
+    let t = t - 2.25 / d;
+    n * t * t + 0.9375
+  } else {
+    let t = t - 2.625 / d;
+    n * t * t + 0.984375
+  }
+}
+
+impl FunctionOutput for Easing {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let t = x / self.duration;
+      let eased = match self.mode {
+        Mode::In => ease_in(self.family, t),
+        Mode::Out => 1.0 - ease_in(self.family, 1.0 - t),
+        Mode::InOut => {
+          if t < 0.5 {
+            0.5 * ease_in(self.family, 2.0 * t)
+          } else {
+            1.0 - 0.5 * ease_in(self.family, 2.0 - 2.0 * t)
+          }
+        }
+      };
+      result = Some(self.values.0 + (self.values.1 - self.values.0) * eased);
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::numeric::float::next_down;
+
+  const FAMILIES: [Family; 9] = [
+    Family::Quad,
+    Family::Cubic,
+    Family::Quart,
+    Family::Sine,
+    Family::Expo,
+    Family::Circ,
+    Family::Back,
+    Family::Elastic,
+    Family::Bounce,
+  ];
+
+  #[test]
+  fn end_points() {
+    for family in FAMILIES.iter() {
+      assert_eq!(0.0, ease_in(*family, 0.0));
+      assert!((ease_in(*family, 1.0) - 1.0).abs() < 1e-15);
+      for mode in [Mode::In, Mode::Out, Mode::InOut].iter() {
+        let a = Easing::new(2.0, *family, *mode, (1.0, 3.0));
+        assert!((a.generate(0.0).unwrap() - 1.0).abs() < 1e-15);
+        assert!((a.generate(next_down(2.0)).unwrap() - 3.0).abs() < 1e-6);
+      }
+    }
+  }
+  #[test]
+  fn in_out_is_continuous() {
+    for family in FAMILIES.iter() {
+      let a = Easing::new(1.0, *family, Mode::InOut, (0.0, 1.0));
+      assert!((a.generate(0.5).unwrap() - 0.5).abs() < 1e-15);
+      // Circ has an infinite slope at the middle so the left limit is only close
+      assert!((a.generate(next_down(0.5)).unwrap() - 0.5).abs() < 1e-6);
+    }
+  }
+  #[test]
+  fn known_values() {
+    assert_eq!(0.25, ease_in(Family::Quad, 0.5));
+    assert_eq!(0.125, ease_in(Family::Cubic, 0.5));
+    assert!((ease_in(Family::Sine, 0.5) - (1.0 - 0.5f64.sqrt())).abs() < 1e-15);
+    assert!((ease_in(Family::Expo, 0.5) - 31.0 / 1023.0).abs() < 1e-15);
+    // Back dips below zero before heading to the end
+    assert!(ease_in(Family::Back, 0.3) < 0.0);
+    // Bounce touches down at 1/2.75
+    assert!((bounce_out(1.0 / 2.75) - 1.0).abs() < 1e-12);
+  }
+  #[test]
+  fn out_mirrors_in() {
+    let a = Easing::new(1.0, Family::Quad, Mode::Out, (0.0, 1.0));
+    assert_eq!(Some(0.75), a.generate(0.5));
+  }
+  #[test]
+  fn smooth() {
+    let a = smoothstep(2.0, (1.0, 3.0));
+    let b = smootherstep(2.0, (1.0, 3.0));
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(2.0), a.generate(1.0));
+    assert_eq!(Some(2.0), b.generate(1.0));
+    assert!((a.generate(0.5).unwrap() - (1.0 + 2.0 * 0.15625)).abs() < 1e-15);
+    assert!((b.generate(0.5).unwrap() - (1.0 + 2.0 * 0.103515625)).abs() < 1e-15);
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Easing::new(1.0, Family::Sine, Mode::In, (0.0, 1.0));
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+}
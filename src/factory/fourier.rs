@@ -0,0 +1,189 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use std::f64::consts::PI;
+
+/// Fourier series defined by its harmonics
+///
+/// The Fourier series requires four inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Frequency: Fundamental frequency in cycles per unit of duration
+/// * Offset: Constant (DC) term of the series
+/// * Harmonics: Coefficients of harmonics 1, 2, 3 and so on
+///
+/// The series is offset + sum a_n cos(2 pi n f x) + b_n sin(2 pi n f x). It
+/// is evaluated with the Clenshaw recurrence of cos(n theta) and sin(n theta)
+/// so every call only needs one sine and one cosine no matter how many
+/// harmonics there are.
+pub struct Fourier {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Fundamental frequency in cycles per unit of duration
+  frequency: f64,
+  // Constant term
+  offset: f64,
+  // Cosine coefficients, index 0 is the fundamental
+  cosine: Vec<f64>,
+  // Sine coefficients, index 0 is the fundamental
+  sine: Vec<f64>,
+}
+
+/// Constructors for the Fourier series
+impl Fourier {
+  /// Creates a new Fourier series from cosine (a_n) and sine (b_n) coefficients
+  ///
+  /// The shorter coefficient vector is padded with zeros.
+  pub fn new(
+    dur: f64,
+    frequency: f64,
+    offset: f64,
+    mut cosine: Vec<f64>,
+    mut sine: Vec<f64>,
+  ) -> Fourier {
+    let len = cosine.len().max(sine.len());
+    cosine.resize(len, 0.0);
+    sine.resize(len, 0.0);
+    Fourier {
+      duration: dur,
+      frequency,
+      offset,
+      cosine,
+      sine,
+    }
+  }
+  /// Creates a new Fourier series from magnitudes and phases
+  ///
+  /// Each harmonic is magnitude * cos(2 pi n f x + phase) with the phase in
+  /// radians. It is an error for there not to be one phase per magnitude.
+  pub fn polar(
+    dur: f64,
+    frequency: f64,
+    offset: f64,
+    magnitude: Vec<f64>,
+    phase: Vec<f64>,
+  ) -> Result<Fourier, Error> {
+    if magnitude.len() != phase.len() {
+      return Err(Error::InvalidInput("Fourier series needs one phase per magnitude"));
+    }
+    let cosine = magnitude.iter().zip(phase.iter()).map(|(m, p)| m * p.cos()).collect();
+    let sine = magnitude.iter().zip(phase.iter()).map(|(m, p)| -m * p.sin()).collect();
+    Ok(Fourier::new(dur, frequency, offset, cosine, sine))
+  }
+  /// Fundamental frequency in cycles per unit of duration
+  pub fn frequency(&self) -> f64 {
+    self.frequency
+  }
+  /// Constant term of the series
+  pub fn offset(&self) -> f64 {
+    self.offset
+  }
+  /// Cosine coefficients, index 0 is the fundamental
+  pub fn cosine(&self) -> &[f64] {
+    &self.cosine
+  }
+  /// Sine coefficients, index 0 is the fundamental
+  pub fn sine(&self) -> &[f64] {
+    &self.sine
+  }
+  /// Magnitude of each harmonic, index 0 is the fundamental
+  pub fn magnitude(&self) -> Vec<f64> {
+    self.cosine.iter().zip(self.sine.iter()).map(|(a, b)| a.hypot(*b)).collect()
+  }
+  /// Phase of each harmonic in radians, index 0 is the fundamental
+  pub fn phase(&self) -> Vec<f64> {
+    self.cosine.iter().zip(self.sine.iter()).map(|(a, b)| (-b).atan2(*a)).collect()
+  }
+}
+
+/// Runs the Clenshaw recurrence of cos(n theta) and sin(n theta)
+///
+/// Both families satisfy phi_(n+1) = 2 cos(theta) phi_n - phi_(n-1) so the
+/// backwards sums only differ in how they are closed off at n = 1.
+fn clenshaw(
+  coeff: &[f64],
+  two_cos: f64,
+) -> (f64, f64) {
+  let mut b1: f64 = 0.0;
+  let mut b2: f64 = 0.0;
+  for c in coeff.iter().rev() {
+    let b0 = c + two_cos * b1 - b2;
+    b2 = b1;
+    b1 = b0;
+  }
+  (b1, b2)
+}
+
+impl FunctionOutput for Fourier {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let (sin, cos) = (2.0 * PI * self.frequency * x).sin_cos();
+      // With the fundamental at index 0: sum a_n cos(n theta) = cos * b1 - b2
+      // and sum b_n sin(n theta) = sin * b1 once the recurrence reaches n = 1
+      let (a1, a2) = clenshaw(&self.cosine, 2.0 * cos);
+      let (s1, _) = clenshaw(&self.sine, 2.0 * cos);
+      result = Some(self.offset + cos * a1 - a2 + sin * s1);
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Direct summation to compare the recurrence against
+  fn direct(
+    a: &Fourier,
+    x: f64,
+  ) -> f64 {
+    let mut out = a.offset();
+    for (n, (c, s)) in a.cosine().iter().zip(a.sine().iter()).enumerate() {
+      let theta = 2.0 * PI * a.frequency() * (n + 1) as f64 * x;
+      out += c * theta.cos() + s * theta.sin();
+    }
+    out
+  }
+
+  #[test]
+  fn fundamental() {
+    let a = Fourier::new(1.0, 1.0, 0.0, vec![0.0], vec![1.0]);
+    assert_eq!(Some(0.0), a.generate(0.0));
+    assert!((a.generate(0.25).unwrap() - 1.0).abs() < 1e-15);
+  }
+  #[test]
+  fn recurrence() {
+    let a = Fourier::new(2.0, 1.5, 0.5, vec![1.0, -0.5, 0.25, 0.0, 0.1], vec![0.0, 2.0, -1.0]);
+    for step in 0..200 {
+      let x = step as f64 / 100.0;
+      assert!((a.generate(x).unwrap() - direct(&a, x)).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn polar() {
+    // cos(theta - pi/2) is sin(theta)
+    let a = Fourier::polar(1.0, 1.0, 0.0, vec![0.0, 2.0], vec![0.0, -PI / 2.0]).unwrap();
+    assert!(a.cosine()[1].abs() < 1e-15);
+    assert!((a.sine()[1] - 2.0).abs() < 1e-15);
+    assert!((a.magnitude()[1] - 2.0).abs() < 1e-15);
+    assert!((a.phase()[1] + PI / 2.0).abs() < 1e-15);
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Fourier::new(1.0, 1.0, 0.0, vec![1.0], vec![]);
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+  #[test]
+  fn polar_mismatch() {
+    let a = Fourier::polar(1.0, 1.0, 0.0, vec![1.0, 2.0], vec![0.0]);
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+  }
+}
@@ -0,0 +1,107 @@
+use crate::factory::polynomial::Polynomial;
+
+/// Creates a cubic Hermite segment
+///
+/// The segment goes from the start value with the start slope to the end
+/// value with the end slope over the duration. Start and end are given as
+/// (value, slope) and the slopes are in output units per unit of duration,
+/// not per unit of interval, so they can be read straight off of a profile.
+///
+/// The segment is an ordinary Polynomial over the interval (0,1).
+pub fn cubic(
+  dur: f64,
+  start: (f64, f64),
+  end: (f64, f64),
+) -> Polynomial {
+  let (p0, m0) = (start.0, start.1 * dur);
+  let (p1, m1) = (end.0, end.1 * dur);
+  let coeff = vec![
+    p0,
+    m0,
+    -3.0 * p0 - 2.0 * m0 + 3.0 * p1 - m1,
+    2.0 * p0 + m0 - 2.0 * p1 + m1,
+  ];
+  Polynomial::new(dur, (0.0, 1.0), coeff, false)
+}
+
+/// Creates a quintic Hermite segment
+///
+/// Same as the cubic segment except start and end are given as (value, slope,
+/// curvature) so acceleration is also continuous where segments meet. Slopes
+/// and curvatures are in output units per unit of duration (squared).
+///
+/// The segment is an ordinary Polynomial over the interval (0,1).
+pub fn quintic(
+  dur: f64,
+  start: (f64, f64, f64),
+  end: (f64, f64, f64),
+) -> Polynomial {
+  let (p0, m0, a0) = (start.0, start.1 * dur, start.2 * dur * dur);
+  let (p1, m1, a1) = (end.0, end.1 * dur, end.2 * dur * dur);
+  let coeff = vec![
+    p0,
+    m0,
+    0.5 * a0,
+    -10.0 * p0 - 6.0 * m0 - 1.5 * a0 + 10.0 * p1 - 4.0 * m1 + 0.5 * a1,
+    15.0 * p0 + 8.0 * m0 + 1.5 * a0 - 15.0 * p1 + 7.0 * m1 - a1,
+    -6.0 * p0 - 3.0 * m0 - 0.5 * a0 + 6.0 * p1 - 3.0 * m1 + 0.5 * a1,
+  ];
+  Polynomial::new(dur, (0.0, 1.0), coeff, false)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::interface::FunctionOutput;
+
+  /// Value, slope and curvature of the segment at the end of its duration
+  fn end_state(poly: &Polynomial) -> (f64, f64, f64) {
+    let dur = poly.duration();
+    let mut out = (0.0, 0.0, 0.0);
+    for (k, c) in poly.coefficients().iter().enumerate() {
+      let kf = k as f64;
+      out.0 += c;
+      out.1 += kf * c / dur;
+      out.2 += kf * (kf - 1.0) * c / (dur * dur);
+    }
+    out
+  }
+
+  #[test]
+  fn cubic_endpoints() {
+    let a = cubic(2.0, (1.0, 0.5), (-1.0, 2.0));
+    assert_eq!(Some(1.0), a.generate(0.0));
+    let end = end_state(&a);
+    assert!((end.0 + 1.0).abs() < 1e-12);
+    assert!((end.1 - 2.0).abs() < 1e-12);
+    // Start slope in output units
+    let slope = (a.generate(1e-6).unwrap() - a.generate(0.0).unwrap()) / 1e-6;
+    assert!((slope - 0.5).abs() < 1e-5);
+  }
+  #[test]
+  fn cubic_line() {
+    // Matching slopes produce a straight line
+    let a = cubic(4.0, (0.0, 1.0), (4.0, 1.0));
+    assert_eq!(&[0.0, 4.0, 0.0, 0.0], a.coefficients());
+    assert_eq!(Some(2.5), a.generate(2.5));
+  }
+  #[test]
+  fn quintic_endpoints() {
+    let a = quintic(0.5, (1.0, -2.0, 4.0), (3.0, 1.0, -8.0));
+    assert_eq!(Some(1.0), a.generate(0.0));
+    let end = end_state(&a);
+    assert!((end.0 - 3.0).abs() < 1e-12);
+    assert!((end.1 - 1.0).abs() < 1e-12);
+    assert!((end.2 + 8.0).abs() < 1e-9);
+    // Start slope and curvature in output units
+    let coeff = a.coefficients();
+    assert!((coeff[1] / 0.5 + 2.0).abs() < 1e-12);
+    assert!((2.0 * coeff[2] / 0.25 - 4.0).abs() < 1e-12);
+  }
+  #[test]
+  fn quintic_rest_to_rest() {
+    // Rest to rest is the classic minimum jerk profile 10t^3 - 15t^4 + 6t^5
+    let a = quintic(1.0, (0.0, 0.0, 0.0), (1.0, 0.0, 0.0));
+    assert_eq!(&[0.0, 0.0, 0.0, 10.0, -15.0, 6.0], a.coefficients());
+  }
+}
@@ -1,3 +1,6 @@
+use crate::factory::polynomial::horner;
+use crate::numeric::poly;
+
 /// This defines the common interface for all subfunction types
 ///
 /// All subfunctions should be able to report how long they are and based on some input should be
@@ -10,4 +13,88 @@ pub trait FunctionOutput {
     &self,
     x: f64,
   ) -> Option<f64>;
+  /// Exact piecewise polynomial form of the output, if it has one
+  ///
+  /// Analysis uses the pieces to work exactly instead of numerically. The
+  /// pieces are sorted, cover the limits without gaps and reproduce generate
+  /// everywhere inside of them. Subfunctions that are not polynomial return
+  /// None, which is the default.
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    None
+  }
+  /// Sorted locations where the output may jump or change form
+  ///
+  /// Always includes both limits. The default uses the ends of the pieces
+  /// when there are pieces and only the limits otherwise.
+  fn breakpoints(&self) -> Vec<f64> {
+    match self.pieces() {
+      Some(pieces) if !pieces.is_empty() => {
+        let mut out: Vec<f64> = pieces.iter().map(|p| p.start).collect();
+        out.push(pieces[pieces.len() - 1].end);
+        out
+      }
+      _ => {
+        let limits = self.get_limits();
+        vec![limits.0, limits.1]
+      }
+    }
+  }
+}
+
+/// Polynomial piece of a piecewise polynomial output
+///
+/// The piece is valid over [start,end) and its coefficients are in the
+/// power basis of the local variable x - start, index 0 is the coefficient
+/// of (x - start)^0.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Piece {
+  /// Start of the piece
+  pub start: f64,
+  /// End of the piece
+  pub end: f64,
+  /// Coefficients in powers of x - start
+  pub coefficients: Vec<f64>,
+}
+
+/// Adds overlapping pieces together into one piece per interval
+///
+/// Every start and end splits the limits so each interval is either fully
+/// inside of a piece or fully outside of it.
+pub(crate) fn merge(
+  limits: (f64, f64),
+  parts: &[Piece],
+) -> Vec<Piece> {
+  let mut edges = vec![limits.0, limits.1];
+  for piece in parts.iter() {
+    edges.push(piece.start);
+    edges.push(piece.end);
+  }
+  edges.retain(|x| (*x >= limits.0) && (*x <= limits.1));
+  edges.sort_by(|a, b| a.total_cmp(b));
+  edges.dedup();
+
+  let mut out = Vec::with_capacity(edges.len());
+  for window in edges.windows(2) {
+    let (start, end) = (window[0], window[1]);
+    let mut coefficients = vec![0.0];
+    for piece in parts.iter().filter(|p| (p.start <= start) && (end <= p.end)) {
+      poly::add_assign(&mut coefficients, &poly::shift(&piece.coefficients, start - piece.start));
+    }
+    out.push(Piece { start, end, coefficients });
+  }
+  out
+}
+
+/// Evaluates sorted pieces at x, None outside of them
+pub(crate) fn evaluate(
+  pieces: &[Piece],
+  x: f64,
+) -> Option<f64> {
+  let k = pieces.partition_point(|p| p.start <= x);
+  let piece = pieces.get(k.checked_sub(1)?)?;
+  if x < piece.end {
+    Some(horner(&piece.coefficients, x - piece.start))
+  } else {
+    None
+  }
 }
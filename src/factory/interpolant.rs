@@ -0,0 +1,408 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::{FunctionOutput, Piece};
+use crate::factory::mapping::Mapping;
+use crate::numeric::poly;
+
+/// Piecewise cubic interpolant through a table of knots
+///
+/// The interpolant requires three inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Knots x: Strictly increasing knot locations, the first to last knot is mapped onto the duration
+/// * Knots y: Values at each knot, the interpolant passes through every one of them
+///
+/// Each knot interval is a cubic Hermite segment and the only difference
+/// between the constructors is how the slopes at the knots are chosen.
+/// * PCHIP (Fritsch-Carlson) never overshoots the data so monotonic data
+///   produces a monotonic interpolant.
+/// * Akima uses the modified (makima) weights of the neighbouring secant
+///   slopes so outliers only affect the intervals next to them. It avoids the
+///   wiggles of a natural spline and stays flat where the data is flat. The
+///   slopes are then limited like PCHIP so monotonic data produces a
+///   monotonic interpolant as well.
+pub struct Interpolant {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Map from the duration onto the knot locations
+  mapping: Mapping,
+  // Knot locations
+  x: Vec<f64>,
+  // Knot values
+  y: Vec<f64>,
+  // Slope of the interpolant at each knot
+  slopes: Vec<f64>,
+}
+
+/// Constructors for the Interpolant
+impl Interpolant {
+  /// Creates a monotone piecewise cubic (PCHIP) interpolant
+  ///
+  /// Returns Error::InvalidInput when the knots do not describe a function.
+  pub fn pchip(
+    dur: f64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+  ) -> Result<Interpolant, Error> {
+    check_knots(&x, &y)?;
+    let slopes = pchip_slopes(&x, &y);
+    Ok(Interpolant::new(dur, x, y, slopes))
+  }
+  /// Creates an Akima interpolant
+  ///
+  /// Returns Error::InvalidInput when the knots do not describe a function.
+  pub fn akima(
+    dur: f64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+  ) -> Result<Interpolant, Error> {
+    check_knots(&x, &y)?;
+    let slopes = akima_slopes(&x, &y);
+    Ok(Interpolant::new(dur, x, y, slopes))
+  }
+  fn new(
+    dur: f64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    slopes: Vec<f64>,
+  ) -> Interpolant {
+    Interpolant {
+      duration: dur,
+      mapping: Mapping::new(dur, (x[0], x[x.len() - 1]), false),
+      x,
+      y,
+      slopes,
+    }
+  }
+  /// Slope of the interpolant at each knot in knot units
+  pub fn slopes(&self) -> &[f64] {
+    &self.slopes
+  }
+}
+
+/// Makes sure the knot table describes a function
+fn check_knots(
+  x: &[f64],
+  y: &[f64],
+) -> Result<(), Error> {
+  if x.len() < 2 {
+    return Err(Error::InvalidInput("interpolant needs at least two knots"));
+  }
+  if x.len() != y.len() {
+    return Err(Error::InvalidInput("knot x and y must be the same length"));
+  }
+  if !x.windows(2).all(|w| w[1] > w[0]) {
+    return Err(Error::InvalidInput("knot x must be strictly increasing"));
+  }
+  Ok(())
+}
+
+/// Secant slope of every knot interval
+fn secants(
+  x: &[f64],
+  y: &[f64],
+) -> Vec<f64> {
+  (0..x.len() - 1).map(|k| (y[k + 1] - y[k]) / (x[k + 1] - x[k])).collect()
+}
+
+/// Fritsch-Carlson slopes
+///
+/// Interior slopes are the weighted harmonic mean of the neighbouring secants
+/// (zero at a local extremum) and the end slopes use the shape preserving
+/// three point formula.
+fn pchip_slopes(
+  x: &[f64],
+  y: &[f64],
+) -> Vec<f64> {
+  let n = x.len();
+  let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+  let delta = secants(x, y);
+  if n == 2 {
+    return vec![delta[0], delta[0]];
+  }
+
+  let mut d = vec![0.0; n];
+  for k in 1..n - 1 {
+    if delta[k - 1] * delta[k] > 0.0 {
+      let w1 = 2.0 * h[k] + h[k - 1];
+      let w2 = h[k] + 2.0 * h[k - 1];
+      d[k] = (w1 + w2) / (w1 / delta[k - 1] + w2 / delta[k]);
+    }
+  }
+  d[0] = pchip_end_slope(h[0], h[1], delta[0], delta[1]);
+  d[n - 1] = pchip_end_slope(h[n - 2], h[n - 3], delta[n - 2], delta[n - 3]);
+  d
+}
+
+/// Shape preserving three point end slope
+fn pchip_end_slope(
+  h0: f64,
+  h1: f64,
+  delta0: f64,
+  delta1: f64,
+) -> f64 {
+  let d = ((2.0 * h0 + h1) * delta0 - h0 * delta1) / (h0 + h1);
+  if d.signum() != delta0.signum() || delta0 == 0.0 {
+    0.0
+  } else if (delta0.signum() != delta1.signum()) && (d.abs() > 3.0 * delta0.abs()) {
+    3.0 * delta0
+  } else {
+    d
+  }
+}
+
+/// Modified Akima (makima) slopes
+///
+/// The secants are extended by two on each end with linear extrapolation and
+/// each knot slope is the weighted average of the secants on either side.
+/// The makima weights add half of the sum of each secant pair to Akima's
+/// difference, so equal secants of the same sign no longer get all of the
+/// weight and a ramp into a flat section does not overshoot. The weighted
+/// slopes are passed through [`limit_slopes`] to preserve monotonicity.
+fn akima_slopes(
+  x: &[f64],
+  y: &[f64],
+) -> Vec<f64> {
+  let n = x.len();
+  let delta = secants(x, y);
+  if n == 2 {
+    return vec![delta[0], delta[0]];
+  }
+
+  // m[k+2] is the secant of interval k
+  let mut m = vec![0.0; n + 3];
+  m[2..n + 1].copy_from_slice(&delta);
+  m[1] = 2.0 * m[2] - m[3];
+  m[0] = 2.0 * m[1] - m[2];
+  m[n + 1] = 2.0 * m[n] - m[n - 1];
+  m[n + 2] = 2.0 * m[n + 1] - m[n];
+
+  let mut d: Vec<f64> = (0..n)
+    .map(|k| {
+      let w1 = (m[k + 3] - m[k + 2]).abs() + 0.5 * (m[k + 3] + m[k + 2]).abs();
+      let w2 = (m[k + 1] - m[k]).abs() + 0.5 * (m[k + 1] + m[k]).abs();
+      if w1 + w2 == 0.0 {
+        0.5 * (m[k + 1] + m[k + 2])
+      } else {
+        (w1 * m[k + 1] + w2 * m[k + 2]) / (w1 + w2)
+      }
+    })
+    .collect();
+  limit_slopes(&delta, &mut d);
+  d
+}
+
+/// Fritsch-Carlson/Hyman monotonicity limiter
+///
+/// Slopes are zero at a local extremum of the data and on both ends of a flat
+/// interval, and must have the sign of the secants next to them. With
+/// alpha and beta the end slopes of an interval over its secant, a Hermite
+/// cubic is monotonic when alpha^2 + beta^2 <= 9, so both are scaled back
+/// onto that circle when they fall outside of it.
+fn limit_slopes(
+  delta: &[f64],
+  d: &mut [f64],
+) {
+  for k in 1..d.len() - 1 {
+    if delta[k - 1] * delta[k] <= 0.0 {
+      d[k] = 0.0;
+    }
+  }
+  for k in 0..delta.len() {
+    if delta[k] == 0.0 {
+      d[k] = 0.0;
+      d[k + 1] = 0.0;
+      continue;
+    }
+    let alpha = d[k] / delta[k];
+    let beta = d[k + 1] / delta[k];
+    if alpha < 0.0 {
+      d[k] = 0.0;
+    }
+    if beta < 0.0 {
+      d[k + 1] = 0.0;
+    }
+    let radius = alpha.max(0.0).hypot(beta.max(0.0));
+    if radius > 3.0 {
+      let tau = 3.0 / radius;
+      d[k] = tau * alpha.max(0.0) * delta[k];
+      d[k + 1] = tau * beta.max(0.0) * delta[k];
+    }
+  }
+}
+
+impl FunctionOutput for Interpolant {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let u = self.mapping.apply(x);
+      // Interval k satisfies x[k] <= u < x[k+1]
+      let k = (self.x.partition_point(|knot| *knot <= u).max(1) - 1).min(self.x.len() - 2);
+      let h = self.x[k + 1] - self.x[k];
+      let t = (u - self.x[k]) / h;
+      let t2 = t * t;
+      let t3 = t2 * t;
+      result = Some(
+        (2.0 * t3 - 3.0 * t2 + 1.0) * self.y[k]
+          + (t3 - 2.0 * t2 + t) * h * self.slopes[k]
+          + (-2.0 * t3 + 3.0 * t2) * self.y[k + 1]
+          + (t3 - t2) * h * self.slopes[k + 1],
+      );
+    }
+    result
+  }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    let n = self.x.len();
+    let scale = self.duration / (self.x[n - 1] - self.x[0]);
+    let mut out = Vec::with_capacity(n - 1);
+    for k in 0..n - 1 {
+      let h = self.x[k + 1] - self.x[k];
+      let (y0, y1) = (self.y[k], self.y[k + 1]);
+      let (m0, m1) = (h * self.slopes[k], h * self.slopes[k + 1]);
+      // Hermite cubic of the knot interval in powers of t
+      let hermite = [y0, m0, 3.0 * (y1 - y0) - 2.0 * m0 - m1, 2.0 * (y0 - y1) + m0 + m1];
+      let start = (self.x[k] - self.x[0]) * scale;
+      let end = if k == n - 2 { self.duration } else { (self.x[k + 1] - self.x[0]) * scale };
+      out.push(Piece {
+        start,
+        end,
+        coefficients: poly::compose(&hermite, 1.0 / (h * scale), 0.0),
+      });
+    }
+    Some(out)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn pieces() {
+    let a = Interpolant::akima(2.0, vec![0.0, 1.0, 2.5, 3.0, 4.0], vec![1.0, 3.0, -1.0, 0.5, 0.25]).unwrap();
+    let b = a.pieces().unwrap();
+    assert_eq!(vec![0.0, 0.5, 1.25, 1.5, 2.0], a.breakpoints());
+    for step in 0..200 {
+      let x = step as f64 / 100.0;
+      let piece = b.iter().find(|p| p.start <= x && x < p.end).unwrap();
+      let value = crate::factory::polynomial::horner(&piece.coefficients, x - piece.start);
+      assert!((a.generate(x).unwrap() - value).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn knots() {
+    let x = vec![0.0, 1.0, 2.5, 3.0, 4.0];
+    let y = vec![1.0, 3.0, -1.0, 0.5, 0.25];
+    let a = Interpolant::pchip(4.0, x.clone(), y.clone()).unwrap();
+    let b = Interpolant::akima(4.0, x.clone(), y.clone()).unwrap();
+    for (xk, yk) in x.iter().zip(y.iter()).take(4) {
+      assert!((a.generate(*xk).unwrap() - yk).abs() < 1e-12);
+      assert!((b.generate(*xk).unwrap() - yk).abs() < 1e-12);
+    }
+  }
+  /// A calibration table with a sharp step must not overshoot
+  #[test]
+  fn pchip_monotonic() {
+    let x = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = vec![0.0, 0.1, 0.2, 5.0, 5.1, 5.2];
+    let a = Interpolant::pchip(5.0, x, y).unwrap();
+    let mut last = a.generate(0.0).unwrap();
+    for step in 1..500 {
+      let value = a.generate(step as f64 / 100.0).unwrap();
+      assert!(value >= last);
+      assert!((0.0..=5.2).contains(&value));
+      last = value;
+    }
+  }
+  #[test]
+  fn pchip_extremum() {
+    // The slope at a local extremum of the data is flat
+    let a = Interpolant::pchip(2.0, vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 0.0]).unwrap();
+    assert_eq!(0.0, a.slopes()[1]);
+  }
+  #[test]
+  fn akima_linear() {
+    // Akima reproduces straight lines exactly
+    let a = Interpolant::akima(1.0, vec![0.0, 1.0, 3.0, 4.0], vec![1.0, 3.0, 7.0, 9.0]).unwrap();
+    for step in 0..10 {
+      let x = step as f64 / 10.0;
+      assert!((a.generate(x).unwrap() - (1.0 + 8.0 * x)).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn akima_flat() {
+    // A flat section stays flat next to a step
+    let a = Interpolant::akima(6.0, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]).unwrap();
+    for step in 0..200 {
+      assert_eq!(Some(0.0), a.generate(step as f64 / 100.0));
+    }
+    for step in 400..600 {
+      assert!((a.generate(step as f64 / 100.0).unwrap() - 1.0).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn akima_plateau() {
+    // A ramp into a plateau stays inside of the data, plain Akima overshoots it
+    let a = Interpolant::akima(6.0, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0]).unwrap();
+    let mut last = a.generate(0.0).unwrap();
+    for step in 1..600 {
+      let value = a.generate(step as f64 / 100.0).unwrap();
+      assert!(value >= last - 1e-15);
+      assert!((0.0..=1.0 + 1e-15).contains(&value));
+      last = value;
+    }
+  }
+  #[test]
+  fn akima_monotonic() {
+    // Monotonic data, including a staircase table, never turns back under Akima
+    let x = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let tables = [
+      vec![0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0],
+      vec![0.0, 0.1, 0.2, 5.0, 5.1, 5.2, 5.3],
+      vec![0.0, 0.01, 3.0, 3.01, 3.02, 9.0, 9.5],
+    ];
+    for y in tables.iter() {
+      let a = Interpolant::akima(6.0, x.clone(), y.clone()).unwrap();
+      let mut last = a.generate(0.0).unwrap();
+      for step in 1..600 {
+        let value = a.generate(step as f64 / 100.0).unwrap();
+        assert!(value >= last - 1e-15);
+        assert!((y[0]..=y[6] + 1e-15).contains(&value));
+        last = value;
+      }
+    }
+  }
+  #[test]
+  fn akima_extremum() {
+    // The slope at a local extremum of the data is flat
+    let a = Interpolant::akima(4.0, vec![0.0, 1.0, 2.0, 3.0, 4.0], vec![0.0, 1.0, 3.0, 2.5, 0.0]).unwrap();
+    assert_eq!(0.0, a.slopes()[2]);
+  }
+  #[test]
+  fn duration() {
+    // The knot span is mapped onto the duration
+    let a = Interpolant::pchip(1.0, vec![10.0, 20.0], vec![0.0, 2.0]).unwrap();
+    assert_eq!(Some(1.0), a.generate(0.5));
+  }
+  #[test]
+  fn invalid() {
+    let unsorted = Interpolant::pchip(1.0, vec![0.0, 2.0, 1.0], vec![0.0, 1.0, 2.0]);
+    let short = Interpolant::akima(1.0, vec![0.0], vec![0.0]);
+    let mismatched = Interpolant::pchip(1.0, vec![0.0, 1.0], vec![0.0]);
+    assert!(matches!(unsorted.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(short.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(mismatched.err(), Some(Error::InvalidInput(_))));
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Interpolant::akima(1.0, vec![0.0, 1.0], vec![0.0, 1.0]).unwrap();
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+}
@@ -0,0 +1,195 @@
+// Make the standard subfunction interface available
+use crate::factory::interface::{FunctionOutput, Piece};
+use crate::factory::mapping::Mapping;
+use crate::factory::polynomial::Polynomial;
+
+/// Legendre series defines a continuous sub-domain
+///
+/// This behaves exactly like a Polynomial except the coefficients are given
+/// in the Legendre basis and the series is evaluated with the Clenshaw
+/// recurrence. High degree series stay accurate when the interval is inside
+/// of [-1,1], which is where the basis is well conditioned.
+///
+pub struct Legendre {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this series is valid over.
+  duration: f64,
+  // The interval for which to define the series.
+  interval: (f64, f64),
+  // Coefficients of the series. The 0 index is the coefficient of P_0(x),
+  // the 1 index is the coefficient of P_1(x) and so on.
+  coefficients: Vec<f64>,
+  // Reverses the output of the series, see Polynomial.
+  reverse: bool,
+  // Map from the duration onto the interval, computed once at construction.
+  mapping: Mapping,
+}
+
+/// Constructor for the Legendre series
+impl Legendre {
+  /// Creates a new Legendre series function
+  pub fn new(
+    dur: f64,
+    interval: (f64, f64),
+    coeff: Vec<f64>,
+    reverse: bool,
+  ) -> Legendre {
+    Legendre {
+      duration: dur,
+      interval,
+      coefficients: coeff,
+      reverse,
+      mapping: Mapping::new(dur, interval, reverse),
+    }
+  }
+  /// Converts a power-basis polynomial into the equivalent Legendre series
+  pub fn from_polynomial(poly: &Polynomial) -> Legendre {
+    // Horner's scheme carried out in the Legendre basis
+    let mut out: Vec<f64> = Vec::new();
+    for c in poly.coefficients().iter().rev() {
+      out = multiply_x(&out);
+      if out.is_empty() {
+        out.push(0.0);
+      }
+      out[0] += c;
+    }
+    Legendre::new(poly.duration(), poly.interval(), out, poly.reverse())
+  }
+  /// Converts this series into the equivalent power-basis polynomial
+  pub fn to_polynomial(&self) -> Polynomial {
+    let n = self.coefficients.len();
+    let mut out = vec![0.0; n];
+    // P_{k-1} and P_k as power-basis coefficients
+    let mut prev: Vec<f64> = vec![1.0];
+    let mut curr: Vec<f64> = vec![0.0, 1.0];
+    for (k, c) in self.coefficients.iter().enumerate() {
+      let basis = if k == 0 { &prev } else { &curr };
+      for (idx, b) in basis.iter().enumerate() {
+        out[idx] += c * b;
+      }
+      if k > 0 {
+        // (k+1) P_{k+1} = (2k+1) x P_k - k P_{k-1}
+        let kf = k as f64;
+        let mut next = vec![0.0; k + 2];
+        for (idx, b) in curr.iter().enumerate() {
+          next[idx + 1] += (2.0 * kf + 1.0) / (kf + 1.0) * b;
+        }
+        for (idx, b) in prev.iter().enumerate() {
+          next[idx] -= kf / (kf + 1.0) * b;
+        }
+        prev = curr;
+        curr = next;
+      }
+    }
+    Polynomial::new(self.duration, self.interval, out, self.reverse)
+  }
+  /// Coefficients of the series, index 0 is the coefficient of P_0(x)
+  pub fn coefficients(&self) -> &[f64] {
+    &self.coefficients
+  }
+}
+
+/// Multiplies a Legendre series by x
+///
+/// Uses x*P_n = ((n+1) P_{n+1} + n P_{n-1}) / (2n+1)
+fn multiply_x(coeff: &[f64]) -> Vec<f64> {
+  let mut out = vec![0.0; coeff.len() + 1];
+  for (n, c) in coeff.iter().enumerate() {
+    let nf = n as f64;
+    out[n + 1] += (nf + 1.0) / (2.0 * nf + 1.0) * c;
+    if n > 0 {
+      out[n - 1] += nf / (2.0 * nf + 1.0) * c;
+    }
+  }
+  while out.len() > 1 && out[out.len() - 1] == 0.0 {
+    out.pop();
+  }
+  out
+}
+
+/// Evaluates a Legendre series with the Clenshaw recurrence
+///
+/// The recurrence is P_{k+1} = alpha_k P_k + beta_k P_{k-1} with
+/// alpha_k = (2k+1) x / (k+1) and beta_k = -k / (k+1).
+fn clenshaw(
+  coeff: &[f64],
+  x: f64,
+) -> f64 {
+  let mut b1: f64 = 0.0;
+  let mut b2: f64 = 0.0;
+  for (k, c) in coeff.iter().enumerate().skip(1).rev() {
+    let kf = k as f64;
+    let alpha = (2.0 * kf + 1.0) * x / (kf + 1.0);
+    let beta = -(kf + 1.0) / (kf + 2.0);
+    let b0 = c + alpha * b1 + beta * b2;
+    b2 = b1;
+    b1 = b0;
+  }
+  match coeff.first() {
+    None => 0.0,
+    Some(c0) => c0 + x * b1 - 0.5 * b2,
+  }
+}
+
+impl FunctionOutput for Legendre {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      result = Some(clenshaw(&self.coefficients, self.mapping.apply(x)));
+    }
+    result
+  }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    self.to_polynomial().pieces()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn legendre() {
+    // P_2(x) = (3x^2 - 1) / 2
+    let a = Legendre::new(2.0, (-1.0, 1.0), vec![0.0, 0.0, 1.0], false);
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(-0.5), a.generate(1.0));
+    assert_eq!(Some(-0.125), a.generate(1.5));
+  }
+  #[test]
+  fn to_polynomial() {
+    // P_3(x) = (5x^3 - 3x) / 2
+    let a = Legendre::new(1.0, (-1.0, 1.0), vec![1.0, 0.0, 0.0, 1.0], false);
+    let b = a.to_polynomial();
+    assert_eq!(&[1.0, -1.5, 0.0, 2.5], b.coefficients());
+    for step in 0..10 {
+      let x = step as f64 / 10.0;
+      assert!((a.generate(x).unwrap() - b.generate(x).unwrap()).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn from_polynomial() {
+    let a = Polynomial::new(1.0, (-1.0, 1.0), vec![3.0, -2.0, 5.0, 0.5, 1.0], true);
+    let b = Legendre::from_polynomial(&a);
+    for step in 0..10 {
+      let x = step as f64 / 10.0;
+      assert!((a.generate(x).unwrap() - b.generate(x).unwrap()).abs() < 1e-12);
+    }
+    let c = b.to_polynomial();
+    for (lhs, rhs) in a.coefficients().iter().zip(c.coefficients().iter()) {
+      assert!((lhs - rhs).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Legendre::new(1.0, (-1.0, 1.0), vec![1.0, 2.0], false);
+    assert_eq!(None, a.generate(-0.5));
+    assert_eq!(None, a.generate(1.0));
+  }
+}
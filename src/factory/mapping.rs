@@ -0,0 +1,74 @@
+use crate::numeric::poly;
+
+/// Affine map from subfunction input onto its interval
+///
+/// Every subfunction that is defined over an interval maps its input
+/// [0,duration) onto that interval. The slope and offset only depend on
+/// the construction parameters so they are computed once instead of on
+/// every call to generate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Mapping {
+  // Change in the interval per unit of duration
+  slope: f64,
+  // Interval value at the start of the duration
+  offset: f64,
+}
+
+impl Mapping {
+  /// Creates the map of [0,dur) onto the interval
+  ///
+  /// A reversed map starts at the end of the interval and moves towards the
+  /// beginning.
+  pub(crate) fn new(
+    dur: f64,
+    interval: (f64, f64),
+    reverse: bool,
+  ) -> Mapping {
+    let slope = (interval.1 - interval.0) / dur;
+    if reverse {
+      Mapping { slope: -slope, offset: interval.1 }
+    } else {
+      Mapping { slope, offset: interval.0 }
+    }
+  }
+  /// Maps the subfunction input onto the interval
+  pub(crate) fn apply(
+    &self,
+    x: f64,
+  ) -> f64 {
+    self.slope.mul_add(x, self.offset)
+  }
+  /// Coefficients of a power-basis polynomial of the interval in terms of the input
+  pub(crate) fn compose(
+    &self,
+    coeff: &[f64],
+  ) -> Vec<f64> {
+    poly::compose(coeff, self.slope, self.offset)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn forward() {
+    let a = Mapping::new(2.0, (-1.0, 1.0), false);
+    assert_eq!(-1.0, a.apply(0.0));
+    assert_eq!(0.0, a.apply(1.0));
+    assert_eq!(1.0, a.apply(2.0));
+  }
+  #[test]
+  fn reverse() {
+    let a = Mapping::new(2.0, (-1.0, 1.0), true);
+    assert_eq!(1.0, a.apply(0.0));
+    assert_eq!(0.0, a.apply(1.0));
+    assert_eq!(-1.0, a.apply(2.0));
+  }
+  #[test]
+  fn compose() {
+    // u^2 over (-1,1) is (x - 1)^2 over a duration of 2
+    let a = Mapping::new(2.0, (-1.0, 1.0), false);
+    assert_eq!(vec![1.0, -2.0, 1.0], a.compose(&[0.0, 0.0, 1.0]));
+  }
+}
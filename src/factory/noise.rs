@@ -0,0 +1,242 @@
+// Make the standard subfunction interface available
+use crate::factory::interface::FunctionOutput;
+use std::f64::consts::PI;
+
+/// Octaves summed together for pink noise
+const PINK_ROWS: u32 = 16;
+
+/// Kind of noise to generate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+  /// White noise uniformly distributed in [-scale,scale)
+  Uniform,
+  /// White noise normally distributed with standard deviation scale
+  Gaussian,
+  /// Pink (1/f) noise in [-scale,scale), Voss-McCartney sum of octaves
+  Pink,
+  /// Smooth value noise in [-scale,scale], random values eased between lattice points
+  Value,
+  /// Smooth Perlin gradient noise in [-scale,scale], zero at every lattice point
+  Perlin,
+}
+
+/// Seeded pseudo-random noise
+///
+/// The noise requires five inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Kind: Which kind of noise to generate
+/// * Rate: Samples (lattice points) per unit of duration
+/// * Scale: Amplitude of the noise, see Kind
+/// * Seed: Seed of the pseudo-random sequence
+///
+/// The output is a pure function of x and the seed, there is no generator
+/// state. Every sample is a hash of the seed and the index of the sample
+/// that x falls in, so the same seed always produces the same noise no
+/// matter what order it is generated in. White and pink noise hold their
+/// value for a whole sample, value and Perlin noise are band-limited to
+/// about half the rate and are smooth between samples.
+pub struct Noise {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Which kind of noise to generate
+  kind: Kind,
+  // Samples per unit of duration
+  rate: f64,
+  // Amplitude of the noise
+  scale: f64,
+  // Seed of the pseudo-random sequence
+  seed: u64,
+}
+
+/// Constructor for the Noise
+impl Noise {
+  /// Creates a new noise function
+  pub fn new(
+    dur: f64,
+    kind: Kind,
+    rate: f64,
+    scale: f64,
+    seed: u64,
+  ) -> Noise {
+    Noise {
+      duration: dur,
+      kind,
+      rate,
+      scale,
+      seed,
+    }
+  }
+  /// Uniform number in [0,1) for a lattice index and stream
+  fn unit(
+    &self,
+    stream: u64,
+    index: i64,
+  ) -> f64 {
+    let hash = splitmix64(splitmix64(self.seed ^ stream.wrapping_mul(0xD1B5_4A32_D192_ED03)) ^ index as u64);
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+  }
+  /// Uniform number in [-1,1) for a lattice index and stream
+  fn signed(
+    &self,
+    stream: u64,
+    index: i64,
+  ) -> f64 {
+    2.0 * self.unit(stream, index) - 1.0
+  }
+  /// Standard normal number for a lattice index with the Box-Muller transform
+  fn gaussian(
+    &self,
+    index: i64,
+  ) -> f64 {
+    // 1 - unit is in (0,1] so the logarithm is finite
+    let radius = (-2.0 * (1.0 - self.unit(0, index)).ln()).sqrt();
+    radius * (2.0 * PI * self.unit(1, index)).cos()
+  }
+  /// Voss-McCartney pink noise, octave k changes every 2^k samples
+  fn pink(
+    &self,
+    index: i64,
+  ) -> f64 {
+    let sum: f64 = (0..PINK_ROWS).map(|k| self.signed(k as u64, index >> k)).sum();
+    sum / PINK_ROWS as f64
+  }
+}
+
+/// SplitMix64 mixing function
+fn splitmix64(x: u64) -> u64 {
+  let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+  z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+  z ^ (z >> 31)
+}
+
+/// Perlin's quintic fade curve, flat in slope and curvature at 0 and 1
+fn fade(t: f64) -> f64 {
+  t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+impl FunctionOutput for Noise {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let position = x * self.rate;
+      let index = position.floor() as i64;
+      let t = position - position.floor();
+      let value = match self.kind {
+        Kind::Uniform => self.signed(0, index),
+        Kind::Gaussian => self.gaussian(index),
+        Kind::Pink => self.pink(index),
+        Kind::Value => {
+          let (left, right) = (self.signed(0, index), self.signed(0, index + 1));
+          left + fade(t) * (right - left)
+        }
+        Kind::Perlin => {
+          // The largest 1D Perlin value is 1/2 so it is doubled to fill [-1,1]
+          let left = self.signed(0, index) * t;
+          let right = self.signed(0, index + 1) * (t - 1.0);
+          2.0 * (left + fade(t) * (right - left))
+        }
+      };
+      result = Some(self.scale * value);
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn samples(
+    a: &Noise,
+    count: usize,
+  ) -> Vec<f64> {
+    // Sample in the middle of evenly spaced bins to stay clear of lattice edges
+    let step = a.get_limits().1 / count as f64;
+    (0..count).map(|idx| a.generate((idx as f64 + 0.5) * step).unwrap()).collect()
+  }
+  fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+  }
+  fn lag_correlation(values: &[f64]) -> f64 {
+    let m = mean(values);
+    let var: f64 = values.iter().map(|v| (v - m) * (v - m)).sum();
+    let cov: f64 = values.windows(2).map(|w| (w[0] - m) * (w[1] - m)).sum();
+    cov / var
+  }
+
+  #[test]
+  fn reproducible() {
+    for kind in [Kind::Uniform, Kind::Gaussian, Kind::Pink, Kind::Value, Kind::Perlin].iter() {
+      let a = Noise::new(10.0, *kind, 100.0, 1.0, 42);
+      let b = Noise::new(10.0, *kind, 100.0, 1.0, 42);
+      let c = Noise::new(10.0, *kind, 100.0, 1.0, 43);
+      // Generating backwards must not change anything
+      let forward = samples(&a, 1000);
+      let mut backward: Vec<f64> = (0..1000).rev().map(|idx| b.generate((idx as f64 + 0.5) * 0.01).unwrap()).collect();
+      backward.reverse();
+      assert_eq!(forward, backward);
+      assert_ne!(forward, samples(&c, 1000));
+    }
+  }
+  #[test]
+  fn uniform() {
+    let a = Noise::new(100.0, Kind::Uniform, 100.0, 2.0, 7);
+    let values = samples(&a, 10000);
+    assert!(values.iter().all(|v| (-2.0..2.0).contains(v)));
+    assert!(mean(&values).abs() < 0.05);
+    // Held for a whole sample
+    assert_eq!(a.generate(0.501), a.generate(0.509));
+  }
+  #[test]
+  fn gaussian() {
+    let a = Noise::new(100.0, Kind::Gaussian, 100.0, 3.0, 7);
+    let values = samples(&a, 10000);
+    let m = mean(&values);
+    let std = (values.iter().map(|v| (v - m) * (v - m)).sum::<f64>() / values.len() as f64).sqrt();
+    assert!(m.abs() < 0.1);
+    assert!((std - 3.0).abs() < 0.1);
+  }
+  #[test]
+  fn pink() {
+    // Pink noise is dominated by the slow octaves so neighbours are correlated
+    let a = Noise::new(100.0, Kind::Pink, 100.0, 1.0, 7);
+    let b = Noise::new(100.0, Kind::Uniform, 100.0, 1.0, 7);
+    let values = samples(&a, 10000);
+    assert!(values.iter().all(|v| (-1.0..1.0).contains(v)));
+    assert!(lag_correlation(&values) > 0.8);
+    assert!(lag_correlation(&samples(&b, 10000)).abs() < 0.05);
+  }
+  #[test]
+  fn value() {
+    // Passes through the lattice values and is continuous between them
+    let a = Noise::new(10.0, Kind::Value, 10.0, 1.0, 7);
+    let b = Noise::new(10.0, Kind::Uniform, 10.0, 1.0, 7);
+    assert_eq!(a.generate(0.3), b.generate(0.3));
+    for step in 0..999 {
+      let x = step as f64 / 100.0;
+      assert!((a.generate(x).unwrap() - a.generate(x + 0.001).unwrap()).abs() < 0.05);
+    }
+  }
+  #[test]
+  fn perlin() {
+    let a = Noise::new(10.0, Kind::Perlin, 10.0, 1.0, 7);
+    assert_eq!(Some(0.0), a.generate(0.3));
+    let values = samples(&a, 10000);
+    assert!(values.iter().all(|v| (-1.0..=1.0).contains(v)));
+    assert!(values.iter().any(|v| v.abs() > 0.1));
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Noise::new(1.0, Kind::Uniform, 10.0, 1.0, 0);
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+}
@@ -1,10 +1,13 @@
 /// Make the standard subfunction interface available
-use crate::factory::interface::FunctionOutput;
+use crate::factory::interface::{FunctionOutput, Piece};
+use crate::factory::mapping::Mapping;
 
 /// Polynomial defines a continuous sub-domain
 ///
 /// This object allows the user to map any interval of a polynomial into a
-/// sub-space for use in a function.
+/// sub-space for use in a function. The polynomial is evaluated with Horner's
+/// scheme, or the compensated Horner scheme for high degree polynomials that
+/// need close to twice the working precision.
 ///
 pub struct Polynomial {
   // Duration insinuates time but it really represents the amount of
@@ -20,6 +23,10 @@ pub struct Polynomial {
   // structure is not always straight-forward and this helps mitigate that
   // heart-ache.
   reverse: bool,
+  // Map from the duration onto the interval, computed once at construction.
+  mapping: Mapping,
+  // Evaluate with the compensated Horner scheme instead of plain Horner.
+  compensated: bool,
 }
 
 /// Constructor for the Polynomial
@@ -31,14 +38,83 @@ impl Polynomial {
     coeff: Vec<f64>,
     reverse: bool,
   ) -> Polynomial {
-    let out = Polynomial {
+    Polynomial {
       duration: dur,
-      interval: interval,
+      interval,
       coefficients: coeff,
-      reverse: reverse,
-    };
+      reverse,
+      mapping: Mapping::new(dur, interval, reverse),
+      compensated: false,
+    }
+  }
+  /// Creates a new polynomial function evaluated with compensated Horner
+  ///
+  /// Compensated Horner tracks the rounding error of every step and adds it
+  /// back at the end. It costs a few times more than plain Horner but is
+  /// worth it for high degree or ill-conditioned polynomials.
+  pub fn new_compensated(
+    dur: f64,
+    interval: (f64, f64),
+    coeff: Vec<f64>,
+    reverse: bool,
+  ) -> Polynomial {
+    let mut out = Polynomial::new(dur, interval, coeff, reverse);
+    out.compensated = true;
     out
   }
+  /// Length that the polynomial is valid over
+  pub fn duration(&self) -> f64 {
+    self.duration
+  }
+  /// Interval of the polynomial that is mapped onto the duration
+  pub fn interval(&self) -> (f64, f64) {
+    self.interval
+  }
+  /// Power-basis coefficients, index 0 is the coefficient of x^0
+  pub fn coefficients(&self) -> &[f64] {
+    &self.coefficients
+  }
+  /// Whether the output of the polynomial is reversed
+  pub fn reverse(&self) -> bool {
+    self.reverse
+  }
+}
+
+/// Evaluates a power-basis polynomial with Horner's scheme
+pub(crate) fn horner(
+  coeff: &[f64],
+  x: f64,
+) -> f64 {
+  let mut out: f64 = 0.0;
+  for c in coeff.iter().rev() {
+    out = out.mul_add(x, *c);
+  }
+  out
+}
+
+/// Evaluates a power-basis polynomial with the compensated Horner scheme
+///
+/// Each product and sum is split into its rounded result and exact error
+/// term (TwoProd/TwoSum) and the errors are run through their own Horner
+/// recurrence, see Graillat, Langlois and Louvet (2005).
+pub(crate) fn compensated_horner(
+  coeff: &[f64],
+  x: f64,
+) -> f64 {
+  let mut out: f64 = 0.0;
+  let mut error: f64 = 0.0;
+  for c in coeff.iter().rev() {
+    // TwoProd using a fused multiply-add for the exact error
+    let prod = out * x;
+    let prod_err = out.mul_add(x, -prod);
+    // TwoSum
+    let sum = prod + c;
+    let z = sum - prod;
+    let sum_err = (prod - (sum - z)) + (c - z);
+    out = sum;
+    error = error.mul_add(x, prod_err + sum_err);
+  }
+  out + error
 }
 
 impl FunctionOutput for Polynomial {
@@ -51,26 +127,54 @@ impl FunctionOutput for Polynomial {
   ) -> Option<f64> {
     let mut result = None;
     if (x >= 0.0) && (x < self.duration) {
-      let new_x: f64;
-      if self.reverse {
-        new_x = self.interval.1 - x / self.duration * (self.interval.1 - self.interval.0);
+      let new_x = self.mapping.apply(x);
+      if self.compensated {
+        result = Some(compensated_horner(&self.coefficients, new_x));
       } else {
-        new_x = x / self.duration * (self.interval.1 - self.interval.0) + self.interval.0;
-      }
-      let mut out: f64 = 0.0;
-      for idx in self.coefficients.iter().enumerate() {
-        out += idx.1 * new_x.powf(idx.0 as f64);
+        result = Some(horner(&self.coefficients, new_x));
       }
-      result = Some(out);
     }
     result
   }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    Some(vec![Piece {
+      start: 0.0,
+      end: self.duration,
+      coefficients: self.mapping.compose(&self.coefficients),
+    }])
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  /// Reference evaluation the polynomial used before Horner's scheme
+  fn power_sum(
+    coeff: &[f64],
+    x: f64,
+  ) -> f64 {
+    let mut out: f64 = 0.0;
+    for idx in coeff.iter().enumerate() {
+      out += idx.1 * x.powf(idx.0 as f64);
+    }
+    out
+  }
+
+  /// Expanded coefficients of (x-1)^n, which is famously ill-conditioned near 1
+  fn binomial(n: usize) -> Vec<f64> {
+    let mut coeff = vec![1.0];
+    for _ in 0..n {
+      let mut next = vec![0.0; coeff.len() + 1];
+      for (idx, c) in coeff.iter().enumerate() {
+        next[idx + 1] += c;
+        next[idx] -= c;
+      }
+      coeff = next;
+    }
+    coeff
+  }
+
   #[test]
   fn polynomial() {
     let a = Polynomial::new(1.0, (0.0, 1.0), vec![4.0, 5.0], false);
@@ -88,4 +192,47 @@ mod tests {
     let a = Polynomial::new(1.0, (0.0, 1.0), vec![4.0, 5.0], true);
     assert_eq!(Some(9.0), a.generate(0.0));
   }
+  #[test]
+  fn pieces() {
+    let a = Polynomial::new(2.0, (1.0, 0.0), vec![4.0, 5.0], true);
+    let b = a.pieces().unwrap();
+    assert_eq!(1, b.len());
+    assert_eq!((0.0, 2.0), (b[0].start, b[0].end));
+    assert_eq!(vec![4.0, 2.5], b[0].coefficients);
+    assert_eq!(vec![0.0, 2.0], a.breakpoints());
+  }
+  /// Horner must agree with the original power sum on well behaved input
+  #[test]
+  fn matches_power_sum() {
+    let coeff = vec![1.5, -2.0, 0.25, 3.0, -1.0, 0.125];
+    let a = Polynomial::new(3.0, (-2.0, 2.0), coeff.clone(), false);
+    let b = Polynomial::new_compensated(3.0, (-2.0, 2.0), coeff.clone(), false);
+    for step in 0..300 {
+      let x = step as f64 / 100.0;
+      let expected = power_sum(&coeff, x / 3.0 * 4.0 - 2.0);
+      assert!((a.generate(x).unwrap() - expected).abs() < 1e-12);
+      assert!((b.generate(x).unwrap() - expected).abs() < 1e-12);
+    }
+  }
+  /// Compensated Horner recovers (x-1)^n where the plain sums fall apart
+  #[test]
+  fn compensated_accuracy() {
+    let coeff = binomial(9);
+    let mut power_err: f64 = 0.0;
+    let mut horner_err: f64 = 0.0;
+    let mut compensated_err: f64 = 0.0;
+    for step in 0..100 {
+      let x = 0.99 + step as f64 * 0.0002;
+      let exact = (x - 1.0).powi(9);
+      power_err = power_err.max((power_sum(&coeff, x) - exact).abs());
+      horner_err = horner_err.max((horner(&coeff, x) - exact).abs());
+      compensated_err = compensated_err.max((compensated_horner(&coeff, x) - exact).abs());
+    }
+    // Both plain sums stay within the a priori bound of 2n eps sum |a_i| |x|^i
+    let bound = 18.0 * f64::EPSILON * horner(&coeff.iter().map(|a| a.abs()).collect::<Vec<f64>>(), 1.01);
+    assert!(power_err < bound);
+    assert!(horner_err < bound);
+    assert!(compensated_err < 1e-20);
+    assert!(compensated_err < horner_err / 1000.0);
+  }
 }
@@ -0,0 +1,300 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::{FunctionOutput, Piece};
+use crate::factory::polynomial::Polynomial;
+use crate::numeric::float::next_down;
+
+/// Creates a constant
+///
+/// Like every subfunction the constant is valid over [0,duration). It is an
+/// ordinary Polynomial with a single coefficient.
+pub fn constant(
+  dur: f64,
+  value: f64,
+) -> Polynomial {
+  Polynomial::new(dur, (0.0, 1.0), vec![value], false)
+}
+
+/// Creates a constant that holds the last value of another subfunction
+///
+/// Subfunctions are not defined at the end of their duration so the held
+/// value is the one just before it, which is the limit from the left.
+pub fn hold(
+  dur: f64,
+  previous: &dyn FunctionOutput,
+) -> Polynomial {
+  let last = next_down(previous.get_limits().1);
+  constant(dur, previous.generate(last).unwrap_or(0.0))
+}
+
+/// Creates a ramp from the start value to the end value over the duration
+///
+/// The ramp is an ordinary Polynomial over the interval (0,1).
+pub fn ramp(
+  dur: f64,
+  values: (f64, f64),
+) -> Polynomial {
+  Polynomial::new(dur, (0.0, 1.0), vec![values.0, values.1 - values.0], false)
+}
+
+/// Step (Heaviside) function
+///
+/// The step requires four inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Edge: Location of the discontinuity inside of the duration
+/// * Values: (low, high) values before and after the edge
+/// * At edge: Value exactly at the edge
+///
+/// Away from the edge the output is low on [0,edge) and high on (edge,dur).
+/// The value at the edge picks the convention at the discontinuity:
+/// * high makes the step right-continuous, low on [0,edge) and high on [edge,dur)
+/// * low makes the step left-continuous, low on [0,edge] and high on (edge,dur)
+/// * the midpoint is the symmetric Heaviside convention H(0) = 1/2
+pub struct Step {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Location of the discontinuity
+  edge: f64,
+  // Values before and after the edge
+  values: (f64, f64),
+  // Value exactly at the edge
+  at_edge: f64,
+}
+
+/// Constructor for the Step
+impl Step {
+  /// Creates a new step function
+  pub fn new(
+    dur: f64,
+    edge: f64,
+    values: (f64, f64),
+    at_edge: f64,
+  ) -> Step {
+    Step {
+      duration: dur,
+      edge,
+      values,
+      at_edge,
+    }
+  }
+}
+
+impl FunctionOutput for Step {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      if x < self.edge {
+        result = Some(self.values.0);
+      } else if x > self.edge {
+        result = Some(self.values.1);
+      } else {
+        result = Some(self.at_edge);
+      }
+    }
+    result
+  }
+  /// Only a right-continuous step matches the [start,end) pieces
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    if (self.edge < 0.0) || ((self.edge == 0.0) && (self.at_edge == self.values.1)) {
+      Some(vec![Piece { start: 0.0, end: self.duration, coefficients: vec![self.values.1] }])
+    } else if self.edge >= self.duration {
+      Some(vec![Piece { start: 0.0, end: self.duration, coefficients: vec![self.values.0] }])
+    } else if self.at_edge == self.values.1 {
+      Some(vec![
+        Piece { start: 0.0, end: self.edge, coefficients: vec![self.values.0] },
+        Piece { start: self.edge, end: self.duration, coefficients: vec![self.values.1] },
+      ])
+    } else {
+      None
+    }
+  }
+  fn breakpoints(&self) -> Vec<f64> {
+    if (self.edge > 0.0) && (self.edge < self.duration) {
+      vec![0.0, self.edge, self.duration]
+    } else {
+      vec![0.0, self.duration]
+    }
+  }
+}
+
+/// Saturating ramp
+///
+/// Holds the start value until the ramp begins, ramps linearly to the end
+/// value and then holds the end value until the end of the duration. The
+/// output is continuous so there is no edge convention to pick, it is the
+/// start value on [0,begin], the end value on [end,dur) and linear between.
+pub struct SaturatingRamp {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Where the ramp begins and ends inside of the duration
+  ramp: (f64, f64),
+  // Values held before and after the ramp
+  values: (f64, f64),
+}
+
+/// Constructor for the SaturatingRamp
+impl SaturatingRamp {
+  /// Creates a new saturating ramp
+  pub fn new(
+    dur: f64,
+    ramp: (f64, f64),
+    values: (f64, f64),
+  ) -> Result<SaturatingRamp, Error> {
+    if ramp.1 <= ramp.0 {
+      return Err(Error::InvalidInput("saturating ramp must end after it begins"));
+    }
+    Ok(SaturatingRamp {
+      duration: dur,
+      ramp,
+      values,
+    })
+  }
+}
+
+impl FunctionOutput for SaturatingRamp {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let fraction = ((x - self.ramp.0) / (self.ramp.1 - self.ramp.0)).clamp(0.0, 1.0);
+      result = Some(self.values.0 + fraction * (self.values.1 - self.values.0));
+    }
+    result
+  }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    let slope = (self.values.1 - self.values.0) / (self.ramp.1 - self.ramp.0);
+    let begin = self.ramp.0.clamp(0.0, self.duration);
+    let end = self.ramp.1.clamp(0.0, self.duration);
+    let mut out = Vec::new();
+    if begin > 0.0 {
+      out.push(Piece { start: 0.0, end: begin, coefficients: vec![self.values.0] });
+    }
+    if end > begin {
+      let value = self.values.0 + (begin - self.ramp.0) * slope;
+      out.push(Piece { start: begin, end, coefficients: vec![value, slope] });
+    }
+    if end < self.duration {
+      out.push(Piece { start: end, end: self.duration, coefficients: vec![self.values.1] });
+    }
+    Some(out)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::numeric::float::next_up;
+
+  #[test]
+  fn constant_value() {
+    let a = constant(2.0, 3.0);
+    assert_eq!(Some(3.0), a.generate(0.0));
+    assert_eq!(Some(3.0), a.generate(1.9));
+    assert_eq!(None, a.generate(2.0));
+  }
+  #[test]
+  fn hold_value() {
+    let a = ramp(1.0, (0.0, 2.0));
+    let b = hold(5.0, &a);
+    assert!((b.generate(0.0).unwrap() - 2.0).abs() < 1e-12);
+    assert_eq!((0.0, 5.0), b.get_limits());
+  }
+  #[test]
+  fn ramp_value() {
+    let a = ramp(2.0, (1.0, -1.0));
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(0.0), a.generate(1.0));
+  }
+  #[test]
+  fn step_right_continuous() {
+    let a = Step::new(2.0, 1.0, (0.0, 1.0), 1.0);
+    assert_eq!(Some(0.0), a.generate(next_down(1.0)));
+    assert_eq!(Some(1.0), a.generate(1.0));
+    assert_eq!(Some(1.0), a.generate(next_up(1.0)));
+  }
+  #[test]
+  fn step_left_continuous() {
+    let a = Step::new(2.0, 1.0, (0.0, 1.0), 0.0);
+    assert_eq!(Some(0.0), a.generate(next_down(1.0)));
+    assert_eq!(Some(0.0), a.generate(1.0));
+    assert_eq!(Some(1.0), a.generate(next_up(1.0)));
+  }
+  #[test]
+  fn step_half() {
+    let a = Step::new(2.0, 1.0, (-1.0, 1.0), 0.0);
+    assert_eq!(Some(-1.0), a.generate(0.5));
+    assert_eq!(Some(0.0), a.generate(1.0));
+    assert_eq!(Some(1.0), a.generate(1.5));
+  }
+  #[test]
+  fn step_at_start() {
+    // An edge at zero still honours the edge value at the first sample
+    let a = Step::new(1.0, 0.0, (0.0, 1.0), 0.5);
+    assert_eq!(Some(0.5), a.generate(0.0));
+    assert_eq!(Some(1.0), a.generate(0.1));
+  }
+  #[test]
+  fn saturating_ramp() {
+    let a = SaturatingRamp::new(4.0, (1.0, 3.0), (2.0, 6.0)).unwrap();
+    assert_eq!(Some(2.0), a.generate(0.0));
+    assert_eq!(Some(2.0), a.generate(1.0));
+    assert_eq!(Some(4.0), a.generate(2.0));
+    assert_eq!(Some(6.0), a.generate(3.0));
+    assert_eq!(Some(6.0), a.generate(3.5));
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Step::new(1.0, 0.5, (0.0, 1.0), 1.0);
+    let b = SaturatingRamp::new(1.0, (0.25, 0.75), (0.0, 1.0)).unwrap();
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+    assert_eq!(None, b.generate(-0.1));
+    assert_eq!(None, b.generate(1.0));
+  }
+  #[test]
+  fn step_pieces() {
+    let a = Step::new(2.0, 1.0, (0.0, 1.0), 1.0);
+    let b = Step::new(2.0, 1.0, (0.0, 1.0), 0.5);
+    assert_eq!(2, a.pieces().unwrap().len());
+    assert_eq!(None, b.pieces());
+    assert_eq!(vec![0.0, 1.0, 2.0], b.breakpoints());
+  }
+  #[test]
+  fn step_at_start_pieces() {
+    // The edge value at zero is only a single piece when it matches the value after the edge
+    let a = Step::new(1.0, 0.0, (0.0, 1.0), 0.5);
+    let b = Step::new(1.0, 0.0, (0.0, 1.0), 1.0);
+    assert_eq!(None, a.pieces());
+    let pieces = b.pieces().unwrap();
+    for step in 0..10 {
+      let x = step as f64 / 10.0;
+      assert_eq!(b.generate(x), crate::factory::interface::evaluate(&pieces, x));
+    }
+  }
+  #[test]
+  fn saturating_ramp_pieces() {
+    let a = SaturatingRamp::new(4.0, (1.0, 3.0), (0.0, 4.0)).unwrap();
+    let b = SaturatingRamp::new(4.0, (-1.0, 1.0), (0.0, 4.0)).unwrap();
+    assert_eq!(vec![0.0, 1.0, 3.0, 4.0], a.breakpoints());
+    assert_eq!(vec![0.0, 2.0], a.pieces().unwrap()[1].coefficients);
+    assert_eq!(vec![2.0, 2.0], b.pieces().unwrap()[0].coefficients);
+  }
+  #[test]
+  fn saturating_ramp_invalid() {
+    let a = SaturatingRamp::new(1.0, (0.5, 0.5), (0.0, 1.0));
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+  }
+}
@@ -0,0 +1,192 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use crate::factory::mapping::Mapping;
+use crate::factory::polynomial::horner;
+use crate::numeric::linear;
+use crate::numeric::roots::polynomial_roots;
+
+/// Rational function defines a continuous sub-domain
+///
+/// The ratio of two polynomials, mapped exactly like a Polynomial. Rational
+/// functions can follow asymptotes that polynomials cannot, but they blow up
+/// wherever the denominator is zero. The denominator is checked when the
+/// function is created and any pole inside of the interval is an error.
+///
+pub struct Rational {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Map from the duration onto the interval, computed once at construction.
+  mapping: Mapping,
+  // Coefficients of the numerator, index 0 is the coefficient of x^0.
+  numerator: Vec<f64>,
+  // Coefficients of the denominator, index 0 is the coefficient of x^0.
+  denominator: Vec<f64>,
+}
+
+/// Constructors for the Rational
+impl Rational {
+  /// Creates a new rational function
+  ///
+  /// Returns Error::Pole with the location of the first pole when the
+  /// denominator is zero anywhere on the closed interval.
+  pub fn new(
+    dur: f64,
+    interval: (f64, f64),
+    numerator: Vec<f64>,
+    denominator: Vec<f64>,
+    reverse: bool,
+  ) -> Result<Rational, Error> {
+    let bounds = (interval.0.min(interval.1), interval.0.max(interval.1));
+    if let Some(pole) = polynomial_roots(&denominator, bounds).first() {
+      return Err(Error::Pole(*pole));
+    }
+    Ok(Rational {
+      duration: dur,
+      mapping: Mapping::new(dur, interval, reverse),
+      numerator,
+      denominator,
+    })
+  }
+  /// Creates the [m/n] Padé approximant of a Taylor series
+  ///
+  /// The Taylor coefficients are about x = 0 and at least m + n + 1 of them
+  /// are required. The approximant has a numerator of degree m and a
+  /// denominator of degree n whose constant term is one, and it matches the
+  /// series up to x^(m+n). It is usually accurate much further from zero than
+  /// the truncated series itself.
+  ///
+  /// Returns Error::InvalidInput when there are too few Taylor coefficients
+  /// and Error::Singular when the approximant does not exist.
+  pub fn pade(
+    dur: f64,
+    interval: (f64, f64),
+    taylor: &[f64],
+    m: usize,
+    n: usize,
+    reverse: bool,
+  ) -> Result<Rational, Error> {
+    if taylor.len() <= m + n {
+      return Err(Error::InvalidInput("Padé approximant needs m + n + 1 Taylor coefficients"));
+    }
+    let c = |k: isize| if k < 0 { 0.0 } else { taylor[k as usize] };
+
+    // Denominator b_1..b_n from sum_j b_j c_(k-j) = 0 for k = m+1..m+n
+    let mut denominator = vec![1.0];
+    if n > 0 {
+      let a: Vec<Vec<f64>> = (1..=n)
+        .map(|row| (1..=n).map(|j| c((m + row) as isize - j as isize)).collect())
+        .collect();
+      let b: Vec<f64> = (1..=n).map(|row| -c((m + row) as isize)).collect();
+      denominator.extend(linear::solve(a, b).ok_or(Error::Singular)?);
+    }
+
+    // Numerator a_k = sum_j b_j c_(k-j)
+    let numerator: Vec<f64> = (0..=m)
+      .map(|k| denominator.iter().enumerate().map(|(j, b)| b * c(k as isize - j as isize)).sum())
+      .collect();
+
+    Rational::new(dur, interval, numerator, denominator, reverse)
+  }
+  /// Power-basis coefficients of the numerator
+  pub fn numerator(&self) -> &[f64] {
+    &self.numerator
+  }
+  /// Power-basis coefficients of the denominator
+  pub fn denominator(&self) -> &[f64] {
+    &self.denominator
+  }
+}
+
+impl FunctionOutput for Rational {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let new_x = self.mapping.apply(x);
+      result = Some(horner(&self.numerator, new_x) / horner(&self.denominator, new_x));
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rational() {
+    // 1 / (1 + x) over (0,1)
+    let a = Rational::new(1.0, (0.0, 1.0), vec![1.0], vec![1.0, 1.0], false).unwrap();
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(0.8), a.generate(0.25));
+  }
+  #[test]
+  fn reverse() {
+    let a = Rational::new(1.0, (0.0, 1.0), vec![1.0], vec![1.0, 1.0], true).unwrap();
+    assert_eq!(Some(0.5), a.generate(0.0));
+  }
+  #[test]
+  fn pole() {
+    // 1 / (1 - x) has a pole at 1
+    let a = Rational::new(1.0, (0.0, 2.0), vec![1.0], vec![1.0, -1.0], false);
+    assert_eq!(Some(Error::Pole(1.0)), a.err());
+    // The pole is outside of (-1,0.5) so this one is fine
+    assert!(Rational::new(1.0, (-1.0, 0.5), vec![1.0], vec![1.0, -1.0], false).is_ok());
+  }
+  #[test]
+  fn double_pole() {
+    // 1 / (x - 0.5)^2 never changes sign but still has a pole
+    let a = Rational::new(1.0, (1.0, 0.0), vec![1.0], vec![0.25, -1.0, 1.0], false);
+    assert_eq!(Some(Error::Pole(0.5)), a.err());
+  }
+  #[test]
+  fn pade_exp() {
+    // [2/2] Padé approximant of e^x is (1 + x/2 + x^2/12) / (1 - x/2 + x^2/12)
+    let taylor = [1.0, 1.0, 0.5, 1.0 / 6.0, 1.0 / 24.0];
+    let a = Rational::pade(1.0, (-1.0, 1.0), &taylor, 2, 2, false).unwrap();
+    let expected_num = [1.0, 0.5, 1.0 / 12.0];
+    let expected_den = [1.0, -0.5, 1.0 / 12.0];
+    for (lhs, rhs) in a.numerator().iter().zip(expected_num.iter()) {
+      assert!((lhs - rhs).abs() < 1e-12);
+    }
+    for (lhs, rhs) in a.denominator().iter().zip(expected_den.iter()) {
+      assert!((lhs - rhs).abs() < 1e-12);
+    }
+    // Good to about 4e-3 at the edge of the interval
+    for step in 0..10 {
+      let x = step as f64 / 10.0;
+      assert!((a.generate(x).unwrap() - (2.0 * x - 1.0).exp()).abs() < 5e-3);
+    }
+  }
+  #[test]
+  fn pade_polynomial() {
+    // [m/0] is the truncated Taylor series
+    let a = Rational::pade(1.0, (0.0, 1.0), &[1.0, 2.0, 3.0], 2, 0, false).unwrap();
+    assert_eq!(&[1.0, 2.0, 3.0], a.numerator());
+    assert_eq!(&[1.0], a.denominator());
+  }
+  #[test]
+  fn pade_singular() {
+    // x has no [0/1] approximant, the constant term would have to be zero
+    let a = Rational::pade(1.0, (0.0, 1.0), &[0.0, 1.0], 0, 1, false);
+    assert_eq!(Some(Error::Singular), a.err());
+  }
+  #[test]
+  fn pade_short() {
+    let a = Rational::pade(1.0, (0.0, 1.0), &[1.0, 1.0, 0.5], 2, 1, false);
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Rational::new(1.0, (0.0, 1.0), vec![1.0], vec![1.0, 1.0], false).unwrap();
+    assert_eq!(None, a.generate(-0.5));
+    assert_eq!(None, a.generate(1.0));
+  }
+}
@@ -0,0 +1,172 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+
+/// Response of an ideal second-order system
+///
+/// The second-order response requires five inputs upon creation:
+/// * Duration: Length that the user wants to use this function for
+/// * Natural frequency: Undamped natural frequency in radians per unit of duration
+/// * Damping: Damping ratio, below 1 is underdamped, 1 is critically damped
+///   and above 1 is overdamped
+/// * Gain: Steady state value, the response to a step of this height
+/// * Initial: (value, slope) at x = 0
+///
+/// The output is the analytic solution of y'' + 2 z w y' + w^2 y = w^2 gain
+/// for x >= 0. A gain of 0 is a free damped oscillation that decays from the
+/// initial conditions and zero initial conditions give the classic step
+/// response.
+pub struct SecondOrder {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Steady state value
+  gain: f64,
+  // Solution of the homogeneous equation for the initial error
+  transient: Transient,
+}
+
+/// Homogeneous solution for each damping regime
+enum Transient {
+  // e^(-decay x) (a cos(omega x) + b sin(omega x))
+  Underdamped { decay: f64, omega: f64, a: f64, b: f64 },
+  // e^(-decay x) (a + b x)
+  Critical { decay: f64, a: f64, b: f64 },
+  // a e^(r1 x) + b e^(r2 x)
+  Overdamped { r1: f64, r2: f64, a: f64, b: f64 },
+}
+
+/// Constructor for the SecondOrder
+impl SecondOrder {
+  /// Creates a new second-order response
+  pub fn new(
+    dur: f64,
+    natural_frequency: f64,
+    damping: f64,
+    gain: f64,
+    initial: (f64, f64),
+  ) -> Result<SecondOrder, Error> {
+    if natural_frequency.is_nan() || (natural_frequency <= 0.0) {
+      return Err(Error::InvalidInput("natural frequency must be positive"));
+    }
+    if damping.is_nan() || (damping < 0.0) {
+      return Err(Error::InvalidInput("damping ratio must not be negative"));
+    }
+    let w = natural_frequency;
+    // The transient is the error from the steady state
+    let (e0, v0) = (initial.0 - gain, initial.1);
+    let transient = if damping < 1.0 {
+      let omega = w * (1.0 - damping * damping).sqrt();
+      let decay = damping * w;
+      Transient::Underdamped { decay, omega, a: e0, b: (v0 + decay * e0) / omega }
+    } else if damping == 1.0 {
+      Transient::Critical { decay: w, a: e0, b: v0 + w * e0 }
+    } else {
+      let root = (damping * damping - 1.0).sqrt();
+      let (r1, r2) = (-w * (damping - root), -w * (damping + root));
+      let a = (v0 - r2 * e0) / (r1 - r2);
+      Transient::Overdamped { r1, r2, a, b: e0 - a }
+    };
+    Ok(SecondOrder {
+      duration: dur,
+      gain,
+      transient,
+    })
+  }
+}
+
+impl FunctionOutput for SecondOrder {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let error = match self.transient {
+        Transient::Underdamped { decay, omega, a, b } => {
+          let (sin, cos) = (omega * x).sin_cos();
+          (-decay * x).exp() * (a * cos + b * sin)
+        }
+        Transient::Critical { decay, a, b } => (-decay * x).exp() * (a + b * x),
+        Transient::Overdamped { r1, r2, a, b } => a * (r1 * x).exp() + b * (r2 * x).exp(),
+      };
+      result = Some(self.gain + error);
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Checks the differential equation with central differences
+  fn residual(
+    a: &SecondOrder,
+    w: f64,
+    z: f64,
+    gain: f64,
+    x: f64,
+  ) -> f64 {
+    let h = 1e-4;
+    let (y0, y1, y2) = (a.generate(x - h).unwrap(), a.generate(x).unwrap(), a.generate(x + h).unwrap());
+    let slope = (y2 - y0) / (2.0 * h);
+    let curvature = (y2 - 2.0 * y1 + y0) / (h * h);
+    curvature + 2.0 * z * w * slope + w * w * (y1 - gain)
+  }
+
+  #[test]
+  fn step_response() {
+    // Standard underdamped overshoot is e^(-pi z / sqrt(1 - z^2))
+    let z: f64 = 0.3;
+    let a = SecondOrder::new(10.0, 2.0, z, 1.0, (0.0, 0.0)).unwrap();
+    let peak_time = std::f64::consts::PI / (2.0 * (1.0 - z * z).sqrt());
+    let overshoot = (-std::f64::consts::PI * z / (1.0 - z * z).sqrt()).exp();
+    assert_eq!(Some(0.0), a.generate(0.0));
+    assert!((a.generate(peak_time).unwrap() - 1.0 - overshoot).abs() < 1e-12);
+  }
+  #[test]
+  fn differential_equation() {
+    for z in [0.0, 0.5, 1.0, 2.5].iter() {
+      let a = SecondOrder::new(5.0, 3.0, *z, 2.0, (0.5, -1.0)).unwrap();
+      assert!((a.generate(0.0).unwrap() - 0.5).abs() < 1e-15);
+      let slope = (a.generate(1e-6).unwrap() - a.generate(0.0).unwrap()) / 1e-6;
+      assert!((slope + 1.0).abs() < 1e-4);
+      for step in 1..40 {
+        assert!(residual(&a, 3.0, *z, 2.0, step as f64 * 0.1).abs() < 1e-4);
+      }
+    }
+  }
+  #[test]
+  fn continuous_in_damping() {
+    // Either side of critical damping must agree with the critical solution
+    let a = SecondOrder::new(5.0, 3.0, 1.0, 1.0, (0.0, 2.0)).unwrap();
+    let b = SecondOrder::new(5.0, 3.0, 1.0 - 1e-9, 1.0, (0.0, 2.0)).unwrap();
+    let c = SecondOrder::new(5.0, 3.0, 1.0 + 1e-9, 1.0, (0.0, 2.0)).unwrap();
+    for step in 0..50 {
+      let x = step as f64 * 0.1;
+      assert!((a.generate(x).unwrap() - b.generate(x).unwrap()).abs() < 1e-6);
+      assert!((a.generate(x).unwrap() - c.generate(x).unwrap()).abs() < 1e-6);
+    }
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = SecondOrder::new(1.0, 1.0, 0.5, 1.0, (0.0, 0.0)).unwrap();
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+  #[test]
+  fn invalid() {
+    let a = SecondOrder::new(1.0, 0.0, 0.5, 1.0, (0.0, 0.0));
+    let b = SecondOrder::new(1.0, 1.0, -0.5, 1.0, (0.0, 0.0));
+    let c = SecondOrder::new(1.0, f64::NAN, 0.5, 1.0, (0.0, 0.0));
+    let d = SecondOrder::new(1.0, 1.0, f64::NAN, 1.0, (0.0, 0.0));
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(b.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(c.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(d.err(), Some(Error::InvalidInput(_))));
+  }
+}
@@ -0,0 +1,158 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::{FunctionOutput, Piece};
+
+/// How a table turns its samples back into a function
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reconstruction {
+  /// Zero-order hold, every sample is held until the next one
+  ZeroOrder,
+  /// First-order hold, straight lines from every sample to the next one
+  FirstOrder,
+}
+
+/// Table of evenly spaced samples
+///
+/// The table requires three inputs upon creation:
+/// * Rate: Samples per unit of duration
+/// * Values: Samples, the first one at zero
+/// * Reconstruction: How the function is made from the samples
+///
+/// Sample n is at n / rate and every sample is valid over one period, so
+/// the duration is the number of samples over the rate. With the first-order
+/// hold the last sample has nothing to move towards and is held over its
+/// period. Both reconstructions are piecewise polynomial so the table has
+/// exact pieces.
+pub struct Table {
+  // Duration insinuates time but it really represents the amount of
+  // space/time/etc. that this function is valid over.
+  duration: f64,
+  // Samples per unit of duration
+  rate: f64,
+  // Samples, the first one at zero
+  values: Vec<f64>,
+  // How the function is made from the samples
+  reconstruction: Reconstruction,
+}
+
+/// Constructor for the Table
+impl Table {
+  /// Creates a new table of samples
+  pub fn new(
+    rate: f64,
+    values: Vec<f64>,
+    reconstruction: Reconstruction,
+  ) -> Result<Table, Error> {
+    if !rate.is_finite() || (rate <= 0.0) {
+      return Err(Error::InvalidInput("table rate must be positive and finite"));
+    }
+    if values.is_empty() {
+      return Err(Error::InvalidInput("table needs at least one sample"));
+    }
+    Ok(Table {
+      duration: values.len() as f64 / rate,
+      rate,
+      values,
+      reconstruction,
+    })
+  }
+  /// Value and slope per sample period over the period of sample index
+  fn segment(
+    &self,
+    index: usize,
+  ) -> (f64, f64) {
+    let value = self.values[index];
+    match self.reconstruction {
+      Reconstruction::ZeroOrder => (value, 0.0),
+      Reconstruction::FirstOrder => (value, self.values.get(index + 1).map_or(0.0, |next| next - value)),
+    }
+  }
+}
+
+impl FunctionOutput for Table {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.duration)
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    let mut result = None;
+    if (x >= 0.0) && (x < self.duration) {
+      let position = x * self.rate;
+      // Rounding can put the very end of the last period on the next index
+      let index = (position.floor() as usize).min(self.values.len() - 1);
+      let (value, slope) = self.segment(index);
+      result = Some(value + slope * (position - index as f64));
+    }
+    result
+  }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    let pieces = (0..self.values.len())
+      .map(|index| {
+        let (value, slope) = self.segment(index);
+        Piece {
+          start: index as f64 / self.rate,
+          end: (index + 1) as f64 / self.rate,
+          coefficients: vec![value, slope * self.rate],
+        }
+      })
+      .collect();
+    Some(pieces)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn zero_order() {
+    let a = Table::new(2.0, vec![1.0, 3.0, -1.0], Reconstruction::ZeroOrder).unwrap();
+    assert_eq!((0.0, 1.5), a.get_limits());
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(1.0), a.generate(0.49));
+    assert_eq!(Some(3.0), a.generate(0.5));
+    assert_eq!(Some(-1.0), a.generate(1.49));
+  }
+  #[test]
+  fn first_order() {
+    let a = Table::new(2.0, vec![1.0, 3.0, -1.0], Reconstruction::FirstOrder).unwrap();
+    assert_eq!(Some(1.0), a.generate(0.0));
+    assert_eq!(Some(2.0), a.generate(0.25));
+    assert_eq!(Some(1.0), a.generate(0.75));
+    // The last sample is held
+    assert_eq!(Some(-1.0), a.generate(1.25));
+  }
+  #[test]
+  fn pieces() {
+    for reconstruction in [Reconstruction::ZeroOrder, Reconstruction::FirstOrder].iter() {
+      let a = Table::new(4.0, vec![0.5, 2.0, 1.0, 4.0], *reconstruction).unwrap();
+      let pieces = a.pieces().unwrap();
+      assert_eq!(4, pieces.len());
+      assert_eq!(1.0, pieces[3].end);
+      for step in 0..100 {
+        let x = step as f64 / 100.0;
+        let exact = crate::factory::interface::evaluate(&pieces, x).unwrap();
+        assert!((a.generate(x).unwrap() - exact).abs() < 1e-12);
+      }
+    }
+  }
+  #[test]
+  fn out_of_bounds() {
+    let a = Table::new(1.0, vec![1.0], Reconstruction::ZeroOrder).unwrap();
+    assert_eq!(None, a.generate(-0.1));
+    assert_eq!(None, a.generate(1.0));
+  }
+  #[test]
+  fn invalid() {
+    let a = Table::new(0.0, vec![1.0], Reconstruction::ZeroOrder);
+    let b = Table::new(1.0, vec![], Reconstruction::FirstOrder);
+    let c = Table::new(f64::NAN, vec![1.0], Reconstruction::ZeroOrder);
+    let d = Table::new(f64::INFINITY, vec![1.0], Reconstruction::ZeroOrder);
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(b.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(c.err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(d.err(), Some(Error::InvalidInput(_))));
+  }
+}
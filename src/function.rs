@@ -1,4 +1,4 @@
-use crate::factory::interface::FunctionOutput;
+use crate::factory::interface::{self, FunctionOutput, Piece};
 
 /// Function joins subfunction subdomains together to form a more
 /// complex function. User can set delay to offset function in
@@ -14,26 +14,24 @@ pub struct Function {
 impl Function {
   /// Creates a new function with default values
   pub fn new() -> Function {
-    let out = Function {
+    Function {
       piecewise: Vec::new(),
       delay: 0.0,
       fcn: Vec::new(),
       limits: (0.0,0.0),
-    };
-    out
+    }
   }
   /// Creates a new function with a delay offset
   ///
   /// A positive delay pushes this function back relative to other functions
   /// and a negative value makes this function lead others.
   pub fn new_delay(delay: f64) -> Function {
-    let out = Function {
+    Function {
       piecewise: Vec::new(),
-      delay: delay,
+      delay,
       fcn: Vec::new(),
       limits: (0.0,0.0),
-    };
-    out
+    }
   }
   /// Supports addition of any output from the Factory
   pub fn add_subfunction(
@@ -82,6 +80,12 @@ impl Function {
   }
 }
 
+impl Default for Function {
+  fn default() -> Function {
+    Function::new()
+  }
+}
+
 impl FunctionOutput for Function {
   fn get_limits(&self) -> (f64,f64) {
     self.limits
@@ -107,22 +111,52 @@ impl FunctionOutput for Function {
 
     // loop through this function first
     for domain in self.piecewise.iter() {
-        result += match domain.generate(fcn_time) {
-          None => 0.0,
-          Some(y) => y,
-        };
+        result += domain.generate(fcn_time).unwrap_or(0.0);
         fcn_time -= domain.get_limits().1;
     }
 
     // Now loop through other functions using the original input
     for fcn in self.fcn.iter() {
-      result += match fcn.generate(x) {
-        None => 0.0,
-        Some(y) => y,
-      };
+      result += fcn.generate(x).unwrap_or(0.0);
     }
     Some(result)
   }
+  /// Pieces of the whole tree added together
+  ///
+  /// Only available when every subfunction and function in the tree has
+  /// pieces. Gaps inside of the limits are zero pieces, just like generate.
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    let mut parts: Vec<Piece> = Vec::new();
+    let mut offset = self.delay;
+    for domain in self.piecewise.iter() {
+      for piece in domain.pieces()? {
+        parts.push(Piece {
+          start: piece.start + offset,
+          end: piece.end + offset,
+          coefficients: piece.coefficients,
+        });
+      }
+      offset += domain.get_limits().1;
+    }
+    for fcn in self.fcn.iter() {
+      parts.extend(fcn.pieces()?);
+    }
+    Some(interface::merge(self.limits, &parts))
+  }
+  fn breakpoints(&self) -> Vec<f64> {
+    let mut out = vec![self.limits.0, self.limits.1];
+    let mut offset = self.delay;
+    for domain in self.piecewise.iter() {
+      out.extend(domain.breakpoints().iter().map(|x| x + offset));
+      offset += domain.get_limits().1;
+    }
+    for fcn in self.fcn.iter() {
+      out.extend(fcn.breakpoints());
+    }
+    out.sort_by(|a, b| a.total_cmp(b));
+    out.dedup();
+    out
+  }
 }
 
 #[cfg(test)]
@@ -273,4 +307,48 @@ mod tests {
     assert_eq!( Some(1.0), a.generate( 1.00) );
     assert_eq!( None     , a.generate( 2.00) );
   }
+  /// Pieces of stacked and delayed functions add up and fill gaps with zero
+  ///        0.0      1.0         2.0         3.0         4.0
+  /// fcn1    [-----------)
+  /// fcn2                         [-----------------------)
+  #[test]
+  fn pieces() {
+    let factory = factory::Factory;
+    let mut a = Function::new();
+    let mut b = Function::new_delay(2.0);
+    a.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+    b.add_subfunction(factory.constant(1.0, 2.0));
+    b.add_subfunction(factory.ramp(1.0, (2.0, 0.0)));
+    a.add_function(b);
+    let c = a.pieces().unwrap();
+    assert_eq!(vec![0.0, 1.0, 2.0, 3.0, 4.0], a.breakpoints());
+    assert_eq!(vec![0.0, 1.0], c[0].coefficients);
+    assert_eq!(vec![0.0], c[1].coefficients);
+    assert_eq!(vec![2.0], c[2].coefficients);
+    assert_eq!(vec![2.0, -2.0], c[3].coefficients);
+  }
+  /// Overlapping pieces are shifted onto the same local variable before adding
+  #[test]
+  fn pieces_overlap() {
+    let factory = factory::Factory;
+    let mut a = Function::new();
+    let mut b = Function::new_delay(1.0);
+    a.add_subfunction(factory.polynomial(2.0, (0.0, 2.0), vec![0.0, 0.0, 1.0], false));
+    b.add_subfunction(factory.constant(2.0, 1.0));
+    a.add_function(b);
+    let c = a.pieces().unwrap();
+    assert_eq!(3, c.len());
+    assert_eq!(vec![2.0, 2.0, 1.0], c[1].coefficients);
+    assert_eq!(vec![1.0], c[2].coefficients);
+  }
+  /// Any subfunction without pieces leaves the whole tree without pieces
+  #[test]
+  fn no_pieces() {
+    let factory = factory::Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+    a.add_subfunction(factory.closure(1.0, (0.0, 1.0), |x| x));
+    assert_eq!(None, a.pieces());
+    assert_eq!(vec![0.0, 1.0, 2.0], a.breakpoints());
+  }
 }
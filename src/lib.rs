@@ -2,7 +2,10 @@
 //!
 //! This library provides tools for the user to synthesize piecewise-functions
 //! using stacked sub-functions. Current sub-functions include user defined
-//! the bump function.
+//! polynomials (power, Chebyshev or Legendre basis), rational functions,
+//! Fourier series, seeded noise, second-order responses, easing curves,
+//! Bézier curves, B-splines and NURBS, PCHIP and Akima interpolants, tables
+//! of samples, user defined closures and the bump function.
 //!
 //! # Hello World
 //!
@@ -21,41 +24,107 @@ pub mod factory;
 /// Module that manages functions
 pub mod function;
 
+/// Module that builds complete functions out of subfunctions
+pub mod builder;
+
+/// Module that combines and transforms functions
+pub mod operator;
+
+/// Module that analyses functions
+pub mod analysis;
+
+/// Module that samples functions
+pub mod sampling;
+
+/// Module that defines the library errors
+pub mod error;
+
+// Numerical routines shared by the rest of the library
+mod numeric;
+
+pub use error::Error;
 pub use factory::Factory;
 pub use function::Function;
 
+use sampling::stream::Sample;
+
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
 
 /// Utility for writing functions to a file
 ///
-///
+/// Every line is x,y of the samples taken by [`Sample::samples`](sampling::stream::Sample::samples)
+/// at the rate over the limits. Samples where the function has no value
+/// are left out and a rate that is not positive writes an empty file.
 pub fn write_to_file(
   fcn: Box<dyn factory::interface::FunctionOutput>,
   sample_rate_hz: f64,
   file_name: String,
 ) {
-  // Use floor because a value >= to the exact duration will return None
-  // and floor is the safer bet.
-  let limits = fcn.get_limits();
-  let num_samples: u64 = ((limits.1-limits.0) * sample_rate_hz).floor() as u64;
-
   let path = Path::new(&file_name);
   let display = path.display();
 
-  let mut file = match File::create(&path) {
+  let mut file = match File::create(path) {
     Err(why) => panic!("Couldn't create {}: {}", display, why),
     Ok(file) => file,
   };
 
-  for step in 0..num_samples {
-    let time = step as f64 / sample_rate_hz + limits.0;
-    let value = fcn.generate(time);
+  // There is nothing to sample without a positive rate
+  let samples = match fcn.samples(sample_rate_hz) {
+    Err(_) => return,
+    Ok(samples) => samples,
+  };
+
+  for (time, value) in samples {
+    if let Err(why) = file.write_all(format!("{},{}\n", time, value).as_bytes()) {
+      panic!("Couldn't write to {}: {}", display, why);
+    }
+  }
+}
 
-    match file.write_all(format!("{},{}\n", time, value.unwrap()).as_bytes()) {
-      Err(why) => panic!("Couldn't write to {}: {}", display, why),
-      Ok(_) => (),
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::interface::FunctionOutput;
+
+  /// Identity over [0,2) without a value over [1,2)
+  struct Gap;
+
+  impl FunctionOutput for Gap {
+    fn get_limits(&self) -> (f64,f64) {
+      (0.0,2.0)
+    }
+    fn generate(
+      &self,
+      x: f64,
+    ) -> Option<f64> {
+      if x < 1.0 {
+        Some(x)
+      } else {
+        None
+      }
     }
   }
+
+  /// Writes the function to a temporary file and reads the file back
+  fn written(
+    fcn: Box<dyn FunctionOutput>,
+    sample_rate_hz: f64,
+    name: &str,
+  ) -> String {
+    let path = std::env::temp_dir().join(name);
+    write_to_file(fcn, sample_rate_hz, path.display().to_string());
+    std::fs::read_to_string(path).unwrap()
+  }
+
+  #[test]
+  fn skips_missing() {
+    assert_eq!("0,0\n0.5,0.5\n", written(Box::new(Gap), 2.0, "piecewise_skips_missing.csv"));
+  }
+  #[test]
+  fn no_rate() {
+    assert_eq!("", written(Box::new(Gap), 0.0, "piecewise_no_rate.csv"));
+    assert_eq!("", written(Box::new(Gap), -1.0, "piecewise_negative_rate.csv"));
+  }
 }
@@ -0,0 +1,23 @@
+/// Real polynomial roots
+pub(crate) mod roots;
+
+/// Dense linear systems
+pub(crate) mod linear;
+
+/// Adaptive numerical integration
+pub(crate) mod quadrature;
+
+/// Power-basis polynomial arithmetic
+pub(crate) mod poly;
+
+/// Bracketed root finding
+pub(crate) mod brent;
+
+/// Finite difference derivatives
+pub(crate) mod differentiate;
+
+/// Fast Fourier transform
+pub(crate) mod fft;
+
+/// Neighbouring floats
+pub(crate) mod float;
@@ -0,0 +1,103 @@
+/// Finds a root of f inside of a bracket with Brent's method
+///
+/// f(a) and f(b) must have opposite signs, or one of them must be zero.
+/// Inverse quadratic interpolation and secant steps are taken while they
+/// shrink the bracket quickly enough, otherwise the bracket is bisected, so
+/// the method never does worse than bisection. Stops once the bracket is
+/// down to adjacent floats or f is exactly zero.
+pub(crate) fn brent<F>(
+  f: &F,
+  mut a: f64,
+  mut b: f64,
+) -> f64
+where
+  F: Fn(f64) -> f64,
+{
+  let mut fa = f(a);
+  let mut fb = f(b);
+  if fa == 0.0 {
+    return a;
+  }
+  if fb == 0.0 {
+    return b;
+  }
+  debug_assert!(fa.signum() != fb.signum(), "brent needs a bracket with a sign change");
+
+  let (mut c, mut fc) = (a, fa);
+  let mut d = b - a;
+  let mut e = d;
+  loop {
+    if fb.signum() == fc.signum() {
+      c = a;
+      fc = fa;
+      d = b - a;
+      e = d;
+    }
+    // Keep b as the best estimate
+    if fc.abs() < fb.abs() {
+      a = b;
+      b = c;
+      c = a;
+      fa = fb;
+      fb = fc;
+      fc = fa;
+    }
+    let tol = 2.0 * f64::EPSILON * b.abs() + f64::MIN_POSITIVE;
+    let half = 0.5 * (c - b);
+    if half.abs() <= tol || fb == 0.0 {
+      return b;
+    }
+    if e.abs() >= tol && fa.abs() > fb.abs() {
+      let s = fb / fa;
+      let (mut p, mut q) = if a == c {
+        // Secant
+        (2.0 * half * s, 1.0 - s)
+      } else {
+        // Inverse quadratic interpolation
+        let q = fa / fc;
+        let r = fb / fc;
+        (s * (2.0 * half * q * (q - r) - (b - a) * (r - 1.0)), (q - 1.0) * (r - 1.0) * (s - 1.0))
+      };
+      if p > 0.0 {
+        q = -q;
+      } else {
+        p = -p;
+      }
+      if 2.0 * p < (3.0 * half * q - (tol * q).abs()).min((e * q).abs()) {
+        e = d;
+        d = p / q;
+      } else {
+        d = half;
+        e = d;
+      }
+    } else {
+      d = half;
+      e = d;
+    }
+    a = b;
+    fa = fb;
+    b += if d.abs() > tol { d } else { tol.copysign(half) };
+    fb = f(b);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn smooth() {
+    let root = brent(&|x: f64| x.cos() - x, 0.0, 1.0);
+    assert!((root - 0.7390851332151607).abs() < 1e-15);
+  }
+  #[test]
+  fn end_point() {
+    assert_eq!(2.0, brent(&|x: f64| x - 2.0, 0.0, 2.0));
+  }
+  #[test]
+  fn discontinuous() {
+    // A jump through zero is narrowed down to the jump itself
+    let root = brent(&|x: f64| if x < 0.3 { -1.0 } else { 1.0 }, 0.0, 1.0);
+    assert!((root - 0.3).abs() < 1e-15);
+  }
+}
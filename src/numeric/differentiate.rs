@@ -0,0 +1,62 @@
+/// Derivative of order 0, 1 or 2 of f at x with finite differences
+///
+/// The stencil stays inside of the segment [lo,hi) so it never reaches over
+/// a breakpoint: central differences are used away from the ends of the
+/// segment and second order accurate one-sided differences near them. The
+/// step is the usual balance between truncation and rounding error for each
+/// order, shrunk to fit short segments.
+pub(crate) fn differentiate<F>(
+  f: &F,
+  x: f64,
+  order: usize,
+  segment: (f64, f64),
+) -> Option<f64>
+where
+  F: Fn(f64) -> Option<f64>,
+{
+  let (lo, hi) = segment;
+  let optimal = match order {
+    0 => return f(x),
+    1 => f64::EPSILON.cbrt(),
+    2 => f64::EPSILON.powf(0.25),
+    _ => panic!("finite differences only support derivatives up to order 2"),
+  };
+  let h = (optimal * x.abs().max(hi - lo).max(1.0)).min((hi - lo) / 8.0);
+  if (x - h >= lo) && (x + h < hi) {
+    let (left, center, right) = (f(x - h)?, f(x)?, f(x + h)?);
+    match order {
+      1 => Some((right - left) / (2.0 * h)),
+      _ => Some((right - 2.0 * center + left) / (h * h)),
+    }
+  } else {
+    // Step towards the inside of the segment
+    let h = if x + 3.0 * h < hi { h } else { -h };
+    let (f0, f1, f2) = (f(x)?, f(x + h)?, f(x + 2.0 * h)?);
+    match order {
+      1 => Some((-3.0 * f0 + 4.0 * f1 - f2) / (2.0 * h)),
+      _ => Some((2.0 * f0 - 5.0 * f1 + 4.0 * f2 - f(x + 3.0 * h)?) / (h * h)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::numeric::float::next_down;
+
+  #[test]
+  fn central() {
+    let f = |x: f64| Some(x.sin());
+    assert!((differentiate(&f, 1.0, 1, (0.0, 2.0)).unwrap() - 1.0f64.cos()).abs() < 1e-9);
+    assert!((differentiate(&f, 1.0, 2, (0.0, 2.0)).unwrap() + 1.0f64.sin()).abs() < 1e-6);
+  }
+  #[test]
+  fn one_sided() {
+    // The kink at 1 must not leak into either side
+    let f = |x: f64| Some(if x < 1.0 { x * x } else { 3.0 - x });
+    assert!((differentiate(&f, 0.0, 1, (0.0, 1.0)).unwrap()).abs() < 1e-9);
+    assert!((differentiate(&f, next_down(1.0), 1, (0.0, 1.0)).unwrap() - 2.0).abs() < 1e-6);
+    assert!((differentiate(&f, next_down(1.0), 2, (0.0, 1.0)).unwrap() - 2.0).abs() < 1e-4);
+    assert!((differentiate(&f, 1.0, 1, (1.0, 2.0)).unwrap() + 1.0).abs() < 1e-9);
+  }
+}
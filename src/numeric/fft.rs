@@ -0,0 +1,128 @@
+use std::f64::consts::PI;
+
+/// Complex number as (real, imaginary)
+pub(crate) type Complex = (f64, f64);
+
+fn mul(
+  a: Complex,
+  b: Complex,
+) -> Complex {
+  (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+/// Discrete Fourier transform X_k = sum x_n e^(-2 pi i k n / N)
+///
+/// Powers of two use the iterative radix-2 Cooley-Tukey algorithm and every
+/// other length is turned into a power of two convolution with Bluestein's
+/// chirp-z algorithm, so every length costs O(N log N).
+pub(crate) fn fft(input: &[Complex]) -> Vec<Complex> {
+  let n = input.len();
+  if n <= 1 {
+    input.to_vec()
+  } else if n.is_power_of_two() {
+    let mut out = input.to_vec();
+    radix2(&mut out, false);
+    out
+  } else {
+    bluestein(input)
+  }
+}
+
+/// In place radix-2 transform, the inverse is left unscaled
+fn radix2(
+  data: &mut [Complex],
+  inverse: bool,
+) {
+  let n = data.len();
+  // Bit reversal permutation
+  let bits = n.trailing_zeros();
+  for idx in 0..n {
+    let rev = idx.reverse_bits() >> (usize::BITS - bits);
+    if rev > idx {
+      data.swap(idx, rev);
+    }
+  }
+  let sign = if inverse { 1.0 } else { -1.0 };
+  let mut len = 2;
+  while len <= n {
+    let angle = sign * 2.0 * PI / len as f64;
+    for chunk in data.chunks_mut(len) {
+      let (lower, upper) = chunk.split_at_mut(len / 2);
+      for (k, (a, b)) in lower.iter_mut().zip(upper.iter_mut()).enumerate() {
+        // Twiddles straight from sin/cos so rounding does not build up
+        let twiddle = (angle * k as f64).sin_cos();
+        let t = mul((twiddle.1, twiddle.0), *b);
+        *b = (a.0 - t.0, a.1 - t.1);
+        *a = (a.0 + t.0, a.1 + t.1);
+      }
+    }
+    len *= 2;
+  }
+}
+
+/// Bluestein's algorithm, nk = (k^2 + n^2 - (k - n)^2) / 2 turns the
+/// transform into a convolution with a chirp
+fn bluestein(input: &[Complex]) -> Vec<Complex> {
+  let n = input.len();
+  let m = (2 * n - 1).next_power_of_two();
+  // e^(-i pi k^2 / n), k^2 is reduced modulo 2n to keep the angle small
+  let chirp: Vec<Complex> = (0..n)
+    .map(|k| {
+      let angle = PI * ((k * k) % (2 * n)) as f64 / n as f64;
+      (angle.cos(), -angle.sin())
+    })
+    .collect();
+  let mut a = vec![(0.0, 0.0); m];
+  for (k, (x, c)) in input.iter().zip(chirp.iter()).enumerate() {
+    a[k] = mul(*x, *c);
+  }
+  let mut b = vec![(0.0, 0.0); m];
+  b[0] = (chirp[0].0, -chirp[0].1);
+  for k in 1..n {
+    let conj = (chirp[k].0, -chirp[k].1);
+    b[k] = conj;
+    b[m - k] = conj;
+  }
+  radix2(&mut a, false);
+  radix2(&mut b, false);
+  for (x, y) in a.iter_mut().zip(b.iter()) {
+    *x = mul(*x, *y);
+  }
+  radix2(&mut a, true);
+  let scale = 1.0 / m as f64;
+  chirp.iter().zip(a.iter()).map(|(c, x)| mul(*c, (x.0 * scale, x.1 * scale))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn dft(input: &[Complex]) -> Vec<Complex> {
+    let n = input.len();
+    (0..n)
+      .map(|k| {
+        input.iter().enumerate().fold((0.0, 0.0), |acc, (j, x)| {
+          let angle = -2.0 * PI * ((k * j) % n) as f64 / n as f64;
+          let t = mul(*x, (angle.cos(), angle.sin()));
+          (acc.0 + t.0, acc.1 + t.1)
+        })
+      })
+      .collect()
+  }
+
+  #[test]
+  fn matches_dft() {
+    for n in [1, 2, 3, 5, 8, 12, 17, 64, 100].iter() {
+      let input: Vec<Complex> = (0..*n).map(|k| ((k as f64 * 0.7).sin(), (k as f64 * 1.3).cos())).collect();
+      for (a, b) in fft(&input).iter().zip(dft(&input).iter()) {
+        assert!((a.0 - b.0).abs() < 1e-10 && (a.1 - b.1).abs() < 1e-10);
+      }
+    }
+  }
+  #[test]
+  fn impulse() {
+    let mut input = vec![(0.0, 0.0); 6];
+    input[0] = (1.0, 0.0);
+    assert!(fft(&input).iter().all(|x| (x.0 - 1.0).abs() < 1e-15 && x.1.abs() < 1e-15));
+  }
+}
@@ -0,0 +1,57 @@
+/// Smallest float greater than x
+///
+/// Steps the bit pattern by one, towards larger magnitudes for positive x and
+/// smaller magnitudes for negative x. Both zeros step to the smallest
+/// positive subnormal, NaN and infinity are returned as they are.
+pub(crate) fn next_up(x: f64) -> f64 {
+  if x.is_nan() || (x == f64::INFINITY) {
+    return x;
+  }
+  let bits = x.to_bits();
+  let magnitude = bits & !(1 << 63);
+  let next = if magnitude == 0 {
+    1
+  } else if bits == magnitude {
+    bits + 1
+  } else {
+    bits - 1
+  };
+  f64::from_bits(next)
+}
+
+/// Largest float less than x
+///
+/// The mirror image of [`next_up`].
+pub(crate) fn next_down(x: f64) -> f64 {
+  -next_up(-x)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn steps() {
+    assert!(next_down(1.0) < 1.0);
+    assert_eq!(1.0, next_up(next_down(1.0)));
+    assert_eq!(1.0 + f64::EPSILON, next_up(1.0));
+    assert_eq!(1.0 - f64::EPSILON / 2.0, next_down(1.0));
+    assert_eq!(-1.0 - f64::EPSILON, next_down(-1.0));
+    assert_eq!(f64::MAX, next_down(f64::INFINITY));
+  }
+  #[test]
+  fn zero() {
+    let tiny = f64::from_bits(1);
+    assert_eq!(tiny, next_up(0.0));
+    assert_eq!(tiny, next_up(-0.0));
+    assert_eq!(-tiny, next_down(0.0));
+    assert_eq!(-tiny, next_down(-0.0));
+    assert_eq!(0.0, next_up(-tiny));
+  }
+  #[test]
+  fn special() {
+    assert!(next_down(f64::NAN).is_nan());
+    assert_eq!(f64::INFINITY, next_up(f64::INFINITY));
+    assert_eq!(f64::NEG_INFINITY, next_down(f64::NEG_INFINITY));
+  }
+}
@@ -0,0 +1,55 @@
+/// Solves the square linear system a * x = b
+///
+/// Gaussian elimination with partial pivoting. Returns None when a pivot
+/// vanishes relative to the size of the matrix, i.e. the system is singular.
+pub(crate) fn solve(
+  mut a: Vec<Vec<f64>>,
+  mut b: Vec<f64>,
+) -> Option<Vec<f64>> {
+  let n = b.len();
+  let scale = a.iter().flatten().fold(0.0f64, |acc, v| acc.max(v.abs()));
+  let tol = scale * n as f64 * f64::EPSILON;
+
+  for col in 0..n {
+    let pivot = (col..n).max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))?;
+    if a[pivot][col].abs() <= tol {
+      return None;
+    }
+    a.swap(col, pivot);
+    b.swap(col, pivot);
+    let pivot_row = a[col].clone();
+    for row in col + 1..n {
+      let factor = a[row][col] / pivot_row[col];
+      for (value, p) in a[row][col..].iter_mut().zip(pivot_row[col..].iter()) {
+        *value -= factor * p;
+      }
+      b[row] -= factor * b[col];
+    }
+  }
+
+  let mut x = vec![0.0; n];
+  for row in (0..n).rev() {
+    let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+    x[row] = (b[row] - sum) / a[row][row];
+  }
+  Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn solve_system() {
+    let a = vec![vec![0.0, 2.0, 1.0], vec![1.0, 1.0, 0.0], vec![3.0, 0.0, 1.0]];
+    let x = solve(a, vec![7.0, 3.0, 6.0]).unwrap();
+    for (value, expected) in x.iter().zip([1.0, 2.0, 3.0].iter()) {
+      assert!((value - expected).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn singular() {
+    let a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+    assert_eq!(None, solve(a, vec![1.0, 2.0]));
+  }
+}
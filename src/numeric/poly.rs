@@ -0,0 +1,108 @@
+/// Coefficients of p(slope * s + offset) in the variable s
+///
+/// Horner's scheme run on polynomials instead of numbers, every step
+/// multiplies the running result by the linear factor.
+pub(crate) fn compose(
+  coeff: &[f64],
+  slope: f64,
+  offset: f64,
+) -> Vec<f64> {
+  let mut out: Vec<f64> = Vec::with_capacity(coeff.len());
+  for c in coeff.iter().rev() {
+    // out = out * (slope * s + offset) + c
+    let mut next = vec![0.0; out.len() + 1];
+    for (idx, value) in out.iter().enumerate() {
+      next[idx] += offset * value;
+      next[idx + 1] += slope * value;
+    }
+    next[0] += c;
+    out = next;
+  }
+  out
+}
+
+/// Coefficients of p(s + shift) in the variable s
+pub(crate) fn shift(
+  coeff: &[f64],
+  shift: f64,
+) -> Vec<f64> {
+  if shift == 0.0 {
+    coeff.to_vec()
+  } else {
+    compose(coeff, 1.0, shift)
+  }
+}
+
+/// Adds b onto a
+pub(crate) fn add_assign(
+  a: &mut Vec<f64>,
+  b: &[f64],
+) {
+  if a.len() < b.len() {
+    a.resize(b.len(), 0.0);
+  }
+  for (value, c) in a.iter_mut().zip(b.iter()) {
+    *value += c;
+  }
+}
+
+/// Coefficients of the derivative
+pub(crate) fn derivative(coeff: &[f64]) -> Vec<f64> {
+  coeff.iter().enumerate().skip(1).map(|(k, c)| k as f64 * c).collect()
+}
+
+/// Coefficients of the antiderivative that is zero at zero
+pub(crate) fn integral(coeff: &[f64]) -> Vec<f64> {
+  let mut out = vec![0.0];
+  out.extend(coeff.iter().enumerate().map(|(k, c)| c / (k + 1) as f64));
+  out
+}
+
+/// Coefficients of the product a * b
+pub(crate) fn multiply(
+  a: &[f64],
+  b: &[f64],
+) -> Vec<f64> {
+  if a.is_empty() || b.is_empty() {
+    return Vec::new();
+  }
+  let mut out = vec![0.0; a.len() + b.len() - 1];
+  for (i, x) in a.iter().enumerate() {
+    for (j, y) in b.iter().enumerate() {
+      out[i + j] += x * y;
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::polynomial::horner;
+
+  #[test]
+  fn compose_linear() {
+    // p(u) = 1 + 2u + 3u^2 at u = 2s - 1
+    let a = compose(&[1.0, 2.0, 3.0], 2.0, -1.0);
+    assert_eq!(vec![2.0, -8.0, 12.0], a);
+    for step in 0..10 {
+      let s = step as f64 / 10.0;
+      assert!((horner(&a, s) - horner(&[1.0, 2.0, 3.0], 2.0 * s - 1.0)).abs() < 1e-14);
+    }
+  }
+  #[test]
+  fn add_and_shift() {
+    let mut a = vec![1.0];
+    add_assign(&mut a, &[1.0, 2.0, 3.0]);
+    assert_eq!(vec![2.0, 2.0, 3.0], a);
+    assert_eq!(vec![2.0, 6.0], derivative(&a));
+    assert_eq!(vec![7.0, 8.0, 3.0], shift(&a, 1.0));
+    assert_eq!(vec![2.0, 2.0, 3.0], shift(&a, 0.0));
+  }
+  #[test]
+  fn integral_and_multiply() {
+    assert_eq!(vec![0.0, 2.0, 1.0, 1.0], integral(&[2.0, 2.0, 3.0]));
+    assert_eq!(vec![-1.0, 0.0, 1.0], multiply(&[1.0, 1.0], &[-1.0, 1.0]));
+    assert!(multiply(&[], &[1.0]).is_empty());
+  }
+}
@@ -0,0 +1,125 @@
+/// Gauss-Kronrod 15 point abscissae, the odd entries are the 7 point Gauss abscissae
+const XGK: [f64; 8] = [
+  0.9914553711208126,
+  0.9491079123427585,
+  0.8648644233597691,
+  0.7415311855993945,
+  0.5860872354676911,
+  0.4058451513773972,
+  0.20778495500789848,
+  0.0,
+];
+
+/// Kronrod weights for XGK
+const WGK: [f64; 8] = [
+  0.022935322010529224,
+  0.06309209262997856,
+  0.10479001032225019,
+  0.14065325971552592,
+  0.1690047266392679,
+  0.19035057806478542,
+  0.20443294007529889,
+  0.20948214108472782,
+];
+
+/// Gauss weights for XGK[1], XGK[3], XGK[5] and XGK[7]
+const WG: [f64; 4] = [
+  0.1294849661688697,
+  0.27970539148927664,
+  0.3818300505051189,
+  0.4179591836734694,
+];
+
+/// Deepest bisection before a panel is accepted regardless of its error
+const MAX_DEPTH: usize = 40;
+
+/// Integrates f over [a,b] with adaptive Gauss-Kronrod quadrature
+///
+/// Each panel is integrated with the 15 point Kronrod rule and the difference
+/// to the embedded 7 point Gauss rule is the error estimate. Panels whose
+/// error is above their share of the tolerance are bisected. Nodes never
+/// land on the panel end points so open intervals are fine.
+pub(crate) fn integrate<F>(
+  f: &F,
+  a: f64,
+  b: f64,
+  tol: f64,
+) -> f64
+where
+  F: Fn(f64) -> f64,
+{
+  adapt(f, a, b, tol, MAX_DEPTH)
+}
+
+fn adapt<F>(
+  f: &F,
+  a: f64,
+  b: f64,
+  tol: f64,
+  depth: usize,
+) -> f64
+where
+  F: Fn(f64) -> f64,
+{
+  let (kronrod, error) = gauss_kronrod(f, a, b);
+  if error <= tol || depth == 0 {
+    kronrod
+  } else {
+    let mid = 0.5 * (a + b);
+    adapt(f, a, mid, 0.5 * tol, depth - 1) + adapt(f, mid, b, 0.5 * tol, depth - 1)
+  }
+}
+
+/// Returns the 15 point Kronrod estimate and its difference to the 7 point Gauss estimate
+fn gauss_kronrod<F>(
+  f: &F,
+  a: f64,
+  b: f64,
+) -> (f64, f64)
+where
+  F: Fn(f64) -> f64,
+{
+  let center = 0.5 * (a + b);
+  let half = 0.5 * (b - a);
+  let f_center = f(center);
+  let mut kronrod = WGK[7] * f_center;
+  let mut gauss = WG[3] * f_center;
+  for idx in 0..7 {
+    let dx = half * XGK[idx];
+    let pair = f(center - dx) + f(center + dx);
+    kronrod += WGK[idx] * pair;
+    if idx % 2 == 1 {
+      gauss += WG[idx / 2] * pair;
+    }
+  }
+  (kronrod * half, ((kronrod - gauss) * half).abs())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn polynomial() {
+    // The Kronrod rule is exact for polynomials up to degree 29
+    let value = integrate(&|x: f64| 5.0 * x.powi(4) - 2.0 * x, 0.0, 2.0, 1e-12);
+    assert!((value - 28.0).abs() < 1e-12);
+  }
+  #[test]
+  fn gauss_is_exact() {
+    // The embedded Gauss rule is exact up to degree 13 so the estimates agree
+    let (kronrod, error) = gauss_kronrod(&|x: f64| x.powi(13) + 1.0, -1.0, 1.0);
+    assert!((kronrod - 2.0).abs() < 1e-14);
+    assert!(error < 1e-14);
+  }
+  #[test]
+  fn oscillatory() {
+    let value = integrate(&|x: f64| (20.0 * x).sin().powi(2), 0.0, std::f64::consts::PI, 1e-12);
+    assert!((value - std::f64::consts::PI / 2.0).abs() < 1e-10);
+  }
+  #[test]
+  fn discontinuous() {
+    let value = integrate(&|x: f64| if x < 0.3 { 1.0 } else { 3.0 }, 0.0, 1.0, 1e-10);
+    assert!((value - 2.4).abs() < 1e-9);
+  }
+}
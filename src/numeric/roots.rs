@@ -0,0 +1,141 @@
+use crate::factory::polynomial::horner;
+
+/// Finds every real root of a power-basis polynomial inside of [lo,hi]
+///
+/// The roots of the derivative split the interval into pieces where the
+/// polynomial is monotonic so each piece holds at most one root, which is
+/// then found by bisection. Roots of even multiplicity do not change sign so
+/// the critical points themselves are also accepted when the polynomial is
+/// zero there to within its rounding error. The roots are sorted.
+///
+/// A polynomial that is identically zero reports the start of the interval.
+pub(crate) fn polynomial_roots(
+  coeff: &[f64],
+  interval: (f64, f64),
+) -> Vec<f64> {
+  let (lo, hi) = interval;
+  let coeff = trim(coeff);
+  let mut out: Vec<f64> = Vec::new();
+  match coeff.len() {
+    0 => out.push(lo),
+    1 => (),
+    2 => {
+      let root = -coeff[0] / coeff[1];
+      if (lo..=hi).contains(&root) {
+        out.push(root);
+      }
+    }
+    _ => {
+      let derivative: Vec<f64> = coeff.iter().enumerate().skip(1).map(|(k, c)| k as f64 * c).collect();
+      let mut points = vec![lo];
+      points.extend(polynomial_roots(&derivative, interval).into_iter().filter(|x| *x > lo && *x < hi));
+      points.push(hi);
+
+      for (idx, a) in points.iter().enumerate() {
+        let fa = horner(coeff, *a);
+        if fa.abs() <= rounding_error(coeff, *a) {
+          out.push(*a);
+          continue;
+        }
+        if let Some(b) = points.get(idx + 1) {
+          let fb = horner(coeff, *b);
+          if fa.signum() != fb.signum() && fb.abs() > rounding_error(coeff, *b) {
+            out.push(bisect(coeff, *a, *b));
+          }
+        }
+      }
+    }
+  }
+  out.dedup_by(|a, b| (*a - *b).abs() <= 4.0 * f64::EPSILON * a.abs().max(1.0));
+  out
+}
+
+/// Drops leading zero coefficients (the highest powers)
+fn trim(coeff: &[f64]) -> &[f64] {
+  let mut len = coeff.len();
+  while len > 0 && coeff[len - 1] == 0.0 {
+    len -= 1;
+  }
+  &coeff[..len]
+}
+
+/// Bound on the rounding error of evaluating the polynomial at x
+fn rounding_error(
+  coeff: &[f64],
+  x: f64,
+) -> f64 {
+  let magnitude: Vec<f64> = coeff.iter().map(|c| c.abs()).collect();
+  4.0 * coeff.len() as f64 * f64::EPSILON * horner(&magnitude, x.abs())
+}
+
+/// Bisects a bracket with a sign change down to adjacent floats
+fn bisect(
+  coeff: &[f64],
+  mut a: f64,
+  mut b: f64,
+) -> f64 {
+  let sign_a = horner(coeff, a).signum();
+  loop {
+    let mid = 0.5 * (a + b);
+    if mid <= a || mid >= b {
+      return mid;
+    }
+    let f_mid = horner(coeff, mid);
+    if f_mid == 0.0 {
+      return mid;
+    }
+    if f_mid.signum() == sign_a {
+      a = mid;
+    } else {
+      b = mid;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn linear() {
+    assert_eq!(vec![0.5], polynomial_roots(&[-1.0, 2.0], (0.0, 1.0)));
+    assert!(polynomial_roots(&[-1.0, 2.0], (0.6, 1.0)).is_empty());
+  }
+  #[test]
+  fn quadratic() {
+    // (x - 0.25)(x - 0.75)
+    let roots = polynomial_roots(&[0.1875, -1.0, 1.0], (0.0, 1.0));
+    assert_eq!(2, roots.len());
+    assert!((roots[0] - 0.25).abs() < 1e-15);
+    assert!((roots[1] - 0.75).abs() < 1e-15);
+  }
+  #[test]
+  fn double_root() {
+    // (x - 0.5)^2 touches zero without changing sign
+    let roots = polynomial_roots(&[0.25, -1.0, 1.0], (0.0, 1.0));
+    assert_eq!(vec![0.5], roots);
+  }
+  #[test]
+  fn endpoint() {
+    let roots = polynomial_roots(&[0.0, 1.0, 1.0], (0.0, 1.0));
+    assert_eq!(vec![0.0], roots);
+  }
+  #[test]
+  fn quintic() {
+    // (x+2)(x+1)x(x-1)(x-2) = x^5 - 5x^3 + 4x
+    let roots = polynomial_roots(&[0.0, 4.0, 0.0, -5.0, 0.0, 1.0], (-3.0, 3.0));
+    assert_eq!(5, roots.len());
+    for (root, expected) in roots.iter().zip([-2.0, -1.0, 0.0, 1.0, 2.0].iter()) {
+      assert!((root - expected).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn no_roots() {
+    assert!(polynomial_roots(&[1.0, 0.0, 1.0], (-5.0, 5.0)).is_empty());
+    assert!(polynomial_roots(&[3.0], (-5.0, 5.0)).is_empty());
+  }
+  #[test]
+  fn zero() {
+    assert_eq!(vec![-1.0], polynomial_roots(&[0.0, 0.0], (-1.0, 1.0)));
+  }
+}
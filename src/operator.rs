@@ -0,0 +1,14 @@
+/// Product of two functions
+pub mod product;
+
+/// Derivative of a function
+pub mod derivative;
+
+/// Inverse of a monotonic function
+pub mod inverse;
+
+/// Convolution and cross-correlation of two functions
+pub mod convolution;
+
+/// Arc length reparameterization of a function
+pub mod arc_length;
@@ -0,0 +1,128 @@
+// Make the standard subfunction interface available
+use crate::factory::interface::FunctionOutput;
+use crate::numeric::brent::brent;
+use crate::numeric::quadrature::integrate;
+use crate::operator::derivative::Derivative;
+
+/// Cells that every breakpoint interval is split into for the length table
+const CELLS: usize = 16;
+
+/// Function reparameterized by the length along its curve
+///
+/// The arc length wrapper requires one input upon creation:
+/// * Function: Curve y = f(x) to traverse
+///
+/// The input of the wrapper is the distance s travelled along the curve
+/// y = f(x) from its start, so its limits run from zero to the length of the
+/// curve and stepping s evenly moves along the curve at constant speed. The
+/// output is y at that distance and [`position`](ArcLength::position) gives
+/// the whole (x, y) point.
+///
+/// A table of the length up to evenly spaced points between the breakpoints
+/// is made at construction. Every lookup finds the table cell the distance
+/// falls in and solves for x inside of it with Brent's method.
+pub struct ArcLength {
+  // Slope of the function, which also owns the function
+  slope: Derivative,
+  // (x, length from the start to x) sorted by both
+  table: Vec<(f64, f64)>,
+}
+
+/// Constructor for the ArcLength
+impl ArcLength {
+  /// Creates the arc length reparameterization of a function
+  pub fn new(fcn: Box<dyn FunctionOutput>) -> ArcLength {
+    let slope = Derivative::slope(fcn);
+    let mut xs = Vec::new();
+    for window in slope.breakpoints().windows(2) {
+      let (a, b) = (window[0], window[1]);
+      if b > a {
+        xs.extend((0..CELLS).map(|k| a + (b - a) * k as f64 / CELLS as f64));
+      }
+    }
+    xs.push(slope.get_limits().1);
+
+    let mut table = Vec::with_capacity(xs.len());
+    let mut length = 0.0;
+    table.push((xs[0], length));
+    for w in xs.windows(2) {
+      length += integrate(&|x| speed(&slope, x), w[0], w[1], 1e-12 * (w[1] - w[0]));
+      table.push((w[1], length));
+    }
+    ArcLength { slope, table }
+  }
+  /// Length of the whole curve
+  pub fn length(&self) -> f64 {
+    self.table[self.table.len() - 1].1
+  }
+  /// Point (x, y) on the curve at distance s from its start
+  pub fn position(
+    &self,
+    s: f64,
+  ) -> Option<(f64, f64)> {
+    if (s < 0.0) || (s >= self.length()) {
+      return None;
+    }
+    let k = self.table.partition_point(|e| e.1 <= s);
+    let (x0, s0) = self.table[k - 1];
+    let (x1, _) = self.table[k];
+    let x = if s == s0 {
+      x0
+    } else {
+      let travelled = |x: f64| s0 + integrate(&|t| speed(&self.slope, t), x0, x, 1e-12 * (x1 - x0)) - s;
+      brent(&travelled, x0, x1)
+    };
+    Some((x, self.slope.function().generate(x).unwrap_or(0.0)))
+  }
+}
+
+/// Length of the curve per unit of x
+fn speed(
+  slope: &Derivative,
+  x: f64,
+) -> f64 {
+  slope.generate(x).unwrap_or(0.0).hypot(1.0)
+}
+
+impl FunctionOutput for ArcLength {
+  fn get_limits(&self) -> (f64,f64) {
+    (0.0,self.length())
+  }
+  fn generate(
+    &self,
+    s: f64,
+  ) -> Option<f64> {
+    self.position(s).map(|p| p.1)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::analysis::geometry::arc_length_over;
+  use crate::factory::Factory;
+
+  #[test]
+  fn line() {
+    let factory = Factory;
+    let a = ArcLength::new(factory.ramp(3.0, (0.0, 4.0)));
+    assert!((a.length() - 5.0).abs() < 1e-14);
+    assert_eq!(Some((0.0, 0.0)), a.position(0.0));
+    let (x, y) = a.position(2.5).unwrap();
+    assert!((x - 1.5).abs() < 1e-14);
+    assert!((y - 2.0).abs() < 1e-14);
+    assert_eq!(None, a.generate(5.0));
+  }
+  #[test]
+  fn constant_speed() {
+    // Equal steps in s cover equal lengths of the curve
+    let factory = Factory;
+    let a = ArcLength::new(factory.closure(4.0, (0.0, 4.0), |x| x.sin()));
+    let b = factory.closure(4.0, (0.0, 4.0), |x| x.sin());
+    let step = a.length() / 100.0;
+    let xs: Vec<f64> = (0..100).map(|k| a.position(k as f64 * step).unwrap().0).collect();
+    for w in xs.windows(2) {
+      assert!((arc_length_over(b.as_ref(), (w[0], w[1])) - step).abs() < 1e-10);
+    }
+  }
+}
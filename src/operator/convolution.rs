@@ -0,0 +1,269 @@
+// Make the standard subfunction interface available
+use crate::factory::interface::{self, FunctionOutput, Piece};
+use crate::numeric::poly;
+use crate::numeric::quadrature::integrate;
+
+/// Convolution or cross-correlation of two functions
+///
+/// The convolution requires two inputs upon creation:
+/// * Left: Function f, e.g. a commanded profile
+/// * Right: Function g, e.g. a smoothing kernel
+///
+/// The convolution is (f * g)(x) = integral of f(t) g(x - t) dt and is
+/// defined from the sum of the starts to the sum of the ends of the limits.
+/// The cross-correlation is (f ⋆ g)(x) = integral of f(t) g(t + x) dt, it
+/// peaks at the shift x that lines g up with f best.
+///
+/// When both functions have pieces the result is worked out exactly once at
+/// construction and has pieces too, the degree of each piece is the sum of
+/// the degrees plus one. Otherwise every call to generate integrates the
+/// overlap with adaptive Gauss-Kronrod quadrature split at the breakpoints
+/// of both functions. Places where either function is None count as zero.
+pub struct Convolution {
+  // Function f
+  lhs: Box<dyn FunctionOutput>,
+  // Function g
+  rhs: Box<dyn FunctionOutput>,
+  // Cross-correlation instead of convolution
+  correlation: bool,
+  // Exact result when both functions have pieces
+  pieces: Option<Vec<Piece>>,
+  // Range of shifts where the functions overlap
+  limits: (f64, f64),
+}
+
+/// Constructors for the Convolution
+impl Convolution {
+  /// Creates the convolution f * g
+  pub fn new(
+    lhs: Box<dyn FunctionOutput>,
+    rhs: Box<dyn FunctionOutput>,
+  ) -> Convolution {
+    let (f, g) = (lhs.get_limits(), rhs.get_limits());
+    let limits = (f.0 + g.0, f.1 + g.1);
+    let pieces = match (lhs.pieces(), rhs.pieces()) {
+      (Some(a), Some(b)) => Some(convolve(&a, &b, limits)),
+      _ => None,
+    };
+    Convolution { lhs, rhs, correlation: false, pieces, limits }
+  }
+  /// Creates the cross-correlation f ⋆ g
+  pub fn correlation(
+    lhs: Box<dyn FunctionOutput>,
+    rhs: Box<dyn FunctionOutput>,
+  ) -> Convolution {
+    let (f, g) = (lhs.get_limits(), rhs.get_limits());
+    let limits = (g.0 - f.1, g.1 - f.0);
+    // Correlating with f is convolving with f mirrored about zero
+    let pieces = match (lhs.pieces(), rhs.pieces()) {
+      (Some(a), Some(b)) => Some(convolve(&mirror(&a), &b, limits)),
+      _ => None,
+    };
+    Convolution { lhs, rhs, correlation: true, pieces, limits }
+  }
+  /// Integrates the overlap numerically
+  fn numeric(
+    &self,
+    x: f64,
+  ) -> f64 {
+    let (f, g) = (self.lhs.get_limits(), self.rhs.get_limits());
+    // Argument of g and the range of t where it is inside of the limits of g
+    let correlation = self.correlation;
+    let argument = move |t: f64| if correlation { t + x } else { x - t };
+    let range = if correlation { (g.0 - x, g.1 - x) } else { (x - g.1, x - g.0) };
+    let (start, end) = (f.0.max(range.0), f.1.min(range.1));
+    if end <= start {
+      return 0.0;
+    }
+    let mut points = vec![start, end];
+    points.extend(self.lhs.breakpoints());
+    points.extend(self.rhs.breakpoints().iter().map(|b| if self.correlation { b - x } else { x - b }));
+    points.retain(|t| (*t >= start) && (*t <= end));
+    points.sort_by(|a, b| a.total_cmp(b));
+    points.dedup();
+
+    let integrand = |t: f64| {
+      let (a, b) = (self.lhs.generate(t), self.rhs.generate(argument(t)));
+      a.unwrap_or(0.0) * b.unwrap_or(0.0)
+    };
+    let tol = 1e-10 * (end - start);
+    points.windows(2).map(|w| integrate(&integrand, w[0], w[1], tol)).sum()
+  }
+}
+
+/// Mirrors pieces about zero, f(x) becomes f(-x)
+fn mirror(pieces: &[Piece]) -> Vec<Piece> {
+  pieces
+    .iter()
+    .rev()
+    .map(|p| Piece {
+      start: -p.end,
+      end: -p.start,
+      coefficients: poly::compose(&p.coefficients, -1.0, p.end - p.start),
+    })
+    .collect()
+}
+
+/// Convolves every pair of pieces exactly and adds the results together
+fn convolve(
+  a: &[Piece],
+  b: &[Piece],
+  limits: (f64, f64),
+) -> Vec<Piece> {
+  let mut parts = Vec::new();
+  for p in a.iter() {
+    for q in b.iter() {
+      parts.extend(convolve_pair(p, q));
+    }
+  }
+  interface::merge(limits, &parts)
+}
+
+/// Convolution of a single pair of pieces
+///
+/// With r = t - p.start and w = x - p.start - q.start the integrand is
+/// p(r) q(w - r), a polynomial in r and w, integrated over r from
+/// max(0, w - q width) to min(p width, w). Both bounds are either constant or
+/// w minus a constant between the breakpoints 0, the two widths and their sum
+/// so the result is one polynomial in w between each of them.
+fn convolve_pair(
+  p: &Piece,
+  q: &Piece,
+) -> Vec<Piece> {
+  let (lp, lq) = (p.end - p.start, q.end - q.start);
+  // terms[j] is the polynomial in r multiplying w^j
+  let mut terms: Vec<Vec<f64>> = vec![Vec::new(); q.coefficients.len()];
+  for (k, a) in p.coefficients.iter().enumerate() {
+    for (l, b) in q.coefficients.iter().enumerate() {
+      // (w - r)^l expanded with binomial coefficients
+      let mut binomial = 1.0;
+      for m in 0..=l {
+        let term = &mut terms[l - m];
+        if term.len() <= k + m {
+          term.resize(k + m + 1, 0.0);
+        }
+        term[k + m] += a * b * binomial * if m % 2 == 0 { 1.0 } else { -1.0 };
+        binomial = binomial * (l - m) as f64 / (m + 1) as f64;
+      }
+    }
+  }
+  let antiderivatives: Vec<Vec<f64>> = terms.iter().map(|t| poly::integral(t)).collect();
+
+  let mut edges = vec![0.0, lp.min(lq), lp.max(lq), lp + lq];
+  edges.dedup();
+  let mut out = Vec::with_capacity(3);
+  for window in edges.windows(2) {
+    let (w0, w1) = (window[0], window[1]);
+    let mid = 0.5 * (w0 + w1);
+    // Bounds as (offset, slope) of w
+    let lower = if mid < lq { (0.0, 0.0) } else { (-lq, 1.0) };
+    let upper = if mid < lp { (0.0, 1.0) } else { (lp, 0.0) };
+    let mut coefficients = vec![0.0];
+    for (j, antiderivative) in antiderivatives.iter().enumerate() {
+      let mut bounded = poly::compose(antiderivative, upper.1, upper.0);
+      let below: Vec<f64> = poly::compose(antiderivative, lower.1, lower.0).iter().map(|c| -c).collect();
+      poly::add_assign(&mut bounded, &below);
+      let mut shifted = vec![0.0; j];
+      shifted.extend(bounded);
+      poly::add_assign(&mut coefficients, &shifted);
+    }
+    out.push(Piece {
+      start: p.start + q.start + w0,
+      end: p.start + q.start + w1,
+      coefficients: poly::shift(&coefficients, w0),
+    });
+  }
+  out
+}
+
+impl FunctionOutput for Convolution {
+  fn get_limits(&self) -> (f64,f64) {
+    self.limits
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    if (x < self.limits.0) || (x >= self.limits.1) {
+      return None;
+    }
+    match &self.pieces {
+      Some(pieces) => interface::evaluate(pieces, x),
+      None => Some(self.numeric(x)),
+    }
+  }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    self.pieces.clone()
+  }
+  /// Kinks of the result are where breakpoints of both functions line up
+  fn breakpoints(&self) -> Vec<f64> {
+    if let Some(pieces) = &self.pieces {
+      let mut out: Vec<f64> = pieces.iter().map(|p| p.start).collect();
+      out.push(self.limits.1);
+      return out;
+    }
+    let (a, b) = (self.lhs.breakpoints(), self.rhs.breakpoints());
+    let mut out = vec![self.limits.0, self.limits.1];
+    for x in a.iter() {
+      out.extend(b.iter().map(|y| if self.correlation { y - x } else { x + y }));
+    }
+    out.retain(|x| (*x >= self.limits.0) && (*x <= self.limits.1));
+    out.sort_by(|a, b| a.total_cmp(b));
+    out.dedup();
+    out
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+
+  #[test]
+  fn boxes() {
+    // Two unit boxes make a triangle
+    let factory = Factory;
+    let a = Convolution::new(factory.constant(1.0, 1.0), factory.constant(1.0, 1.0));
+    assert_eq!((0.0, 2.0), a.get_limits());
+    assert_eq!(2, a.pieces().unwrap().len());
+    assert_eq!(Some(0.0), a.generate(0.0));
+    assert_eq!(Some(0.5), a.generate(0.5));
+    assert_eq!(Some(1.0), a.generate(1.0));
+    assert_eq!(Some(0.5), a.generate(1.5));
+    assert_eq!(None, a.generate(2.0));
+  }
+  #[test]
+  fn exact_matches_numeric() {
+    let factory = Factory;
+    let f = || factory.polynomial(2.0, (-1.0, 1.0), vec![0.5, 1.0, -2.0, 0.5], false);
+    let g = || factory.ramp(0.75, (1.0, -0.5));
+    let f_closure = || factory.closure(2.0, (-1.0, 1.0), |x| 0.5 + x - 2.0 * x * x + 0.5 * x * x * x);
+    let g_closure = || factory.closure(0.75, (0.0, 1.0), |x| 1.0 - 1.5 * x);
+    let a = Convolution::new(f(), g());
+    let b = Convolution::new(f_closure(), g_closure());
+    let c = Convolution::correlation(f(), g());
+    let d = Convolution::correlation(f_closure(), g_closure());
+    assert!(a.pieces().is_some() && b.pieces().is_none());
+    assert_eq!(a.get_limits(), b.get_limits());
+    assert_eq!((-2.0, 0.75), c.get_limits());
+    assert_eq!(c.get_limits(), d.get_limits());
+    for step in 0..275 {
+      let x = step as f64 / 100.0;
+      assert!((a.generate(x).unwrap() - b.generate(x).unwrap()).abs() < 1e-12);
+      let y = x - 2.0;
+      assert!((c.generate(y).unwrap() - d.generate(y).unwrap()).abs() < 1e-12);
+    }
+  }
+  #[test]
+  fn correlation_peak() {
+    // A box correlated with the same box shifted by 3 lines up at a shift of 3
+    let factory = Factory;
+    let mut shifted = crate::function::Function::new_delay(3.0);
+    shifted.add_subfunction(factory.constant(1.0, 1.0));
+    let a = Convolution::correlation(factory.constant(1.0, 1.0), Box::new(shifted));
+    assert_eq!((2.0, 4.0), a.get_limits());
+    assert_eq!(Some(1.0), a.generate(3.0));
+    assert_eq!(Some(0.5), a.generate(2.5));
+    assert_eq!(Some(0.5), a.generate(3.5));
+  }
+}
@@ -0,0 +1,188 @@
+// Make the standard subfunction interface available
+use crate::error::Error;
+use crate::factory::interface::{self, FunctionOutput, Piece};
+use crate::numeric::differentiate::differentiate;
+use crate::numeric::poly;
+
+/// Derivative of a function
+///
+/// The derivative requires two inputs upon creation:
+/// * Function: Function to differentiate
+/// * Order: 1 for the slope (velocity) and 2 for the curvature (acceleration)
+///
+/// Functions with pieces are differentiated exactly, the derivative has
+/// pieces too. Everything else is differentiated with finite differences
+/// that never reach over a breakpoint, so a derivative next to a jump is the
+/// one-sided derivative of the side x is on.
+pub struct Derivative {
+  // Function to differentiate
+  fcn: Box<dyn FunctionOutput>,
+  // Order of the derivative
+  order: usize,
+  // Exact pieces of the derivative when the function has pieces
+  pieces: Option<Vec<Piece>>,
+  // Breakpoints of the function
+  breakpoints: Vec<f64>,
+}
+
+/// Constructor for the Derivative
+impl Derivative {
+  /// Creates the derivative of a function
+  pub fn new(
+    fcn: Box<dyn FunctionOutput>,
+    order: usize,
+  ) -> Result<Derivative, Error> {
+    if !(1..=2).contains(&order) {
+      return Err(Error::InvalidInput("derivative order must be 1 or 2"));
+    }
+    Ok(Derivative::with_order(fcn, order))
+  }
+  /// Creates the first derivative of a function, which cannot fail
+  pub(crate) fn slope(fcn: Box<dyn FunctionOutput>) -> Derivative {
+    Derivative::with_order(fcn, 1)
+  }
+  /// Creates the derivative of an order that is known to be valid
+  fn with_order(
+    fcn: Box<dyn FunctionOutput>,
+    order: usize,
+  ) -> Derivative {
+    let pieces = fcn
+      .pieces()
+      .map(|pieces| differentiate_pieces(&pieces, order));
+    let breakpoints = fcn.breakpoints();
+    Derivative {
+      fcn,
+      order,
+      pieces,
+      breakpoints,
+    }
+  }
+  /// Function that is differentiated
+  pub fn function(&self) -> &dyn FunctionOutput {
+    self.fcn.as_ref()
+  }
+}
+
+/// Differentiates every piece order times
+pub(crate) fn differentiate_pieces(
+  pieces: &[Piece],
+  order: usize,
+) -> Vec<Piece> {
+  pieces
+    .iter()
+    .map(|piece| {
+      let mut coefficients = piece.coefficients.clone();
+      for _ in 0..order {
+        coefficients = poly::derivative(&coefficients);
+      }
+      if coefficients.is_empty() {
+        coefficients.push(0.0);
+      }
+      Piece {
+        start: piece.start,
+        end: piece.end,
+        coefficients,
+      }
+    })
+    .collect()
+}
+
+/// Derivative of a borrowed function, exact when it has pieces
+pub(crate) fn derivative_of(
+  fcn: &dyn FunctionOutput,
+  order: usize,
+) -> impl Fn(f64) -> Option<f64> + '_ {
+  let pieces = fcn
+    .pieces()
+    .map(|pieces| differentiate_pieces(&pieces, order));
+  let breakpoints = fcn.breakpoints();
+  move |x: f64| match &pieces {
+    Some(pieces) => interface::evaluate(pieces, x),
+    None => numeric(fcn, &breakpoints, x, order),
+  }
+}
+
+/// Finite difference derivative of any function that stays between its breakpoints
+pub(crate) fn numeric(
+  fcn: &dyn FunctionOutput,
+  breakpoints: &[f64],
+  x: f64,
+  order: usize,
+) -> Option<f64> {
+  let limits = fcn.get_limits();
+  if (x < limits.0) || (x >= limits.1) {
+    return None;
+  }
+  let k = breakpoints
+    .partition_point(|b| *b <= x)
+    .clamp(1, breakpoints.len() - 1);
+  differentiate(
+    &|x: f64| fcn.generate(x),
+    x,
+    order,
+    (breakpoints[k - 1], breakpoints[k]),
+  )
+}
+
+impl FunctionOutput for Derivative {
+  fn get_limits(&self) -> (f64, f64) {
+    self.fcn.get_limits()
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    match &self.pieces {
+      Some(pieces) => interface::evaluate(pieces, x),
+      None => numeric(self.fcn.as_ref(), &self.breakpoints, x, self.order),
+    }
+  }
+  fn pieces(&self) -> Option<Vec<Piece>> {
+    self.pieces.clone()
+  }
+  fn breakpoints(&self) -> Vec<f64> {
+    self.breakpoints.clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+  use crate::numeric::float::next_down;
+
+  #[test]
+  fn exact() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.polynomial(1.0, (0.0, 1.0), vec![0.0, 0.0, 1.0], false));
+    a.add_subfunction(factory.ramp(1.0, (1.0, 0.0)));
+    let b = Derivative::new(Box::new(a), 1).unwrap();
+    assert_eq!(Some(1.0), b.generate(0.5));
+    assert_eq!(Some(-1.0), b.generate(1.5));
+    assert_eq!(None, b.generate(2.0));
+    assert_eq!(None, b.generate(-1.0));
+    let c = Derivative::new(b.fcn, 2).unwrap();
+    assert_eq!(Some(2.0), c.generate(0.5));
+    assert_eq!(Some(0.0), c.generate(1.5));
+  }
+  #[test]
+  fn numeric() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.closure(1.0, (0.0, 1.0), |x| x * x));
+    a.add_subfunction(factory.closure(1.0, (1.0, 0.0), |x| x));
+    let b = Derivative::new(Box::new(a), 1).unwrap();
+    assert!((b.generate(0.5).unwrap() - 1.0).abs() < 1e-9);
+    // Either side of the kink
+    assert!((b.generate(next_down(1.0)).unwrap() - 2.0).abs() < 1e-6);
+    assert!((b.generate(1.0).unwrap() + 1.0).abs() < 1e-9);
+  }
+  #[test]
+  fn invalid_order() {
+    let factory = Factory;
+    let a = Derivative::new(factory.ramp(1.0, (0.0, 1.0)), 3);
+    assert!(matches!(a.err(), Some(Error::InvalidInput(_))));
+  }
+}
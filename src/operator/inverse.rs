@@ -0,0 +1,209 @@
+// Make the standard subfunction interface available
+use crate::analysis::extrema::{self, Order};
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use crate::numeric::brent::brent;
+use crate::numeric::float::{next_down, next_up};
+
+/// Samples per breakpoint interval used to check monotonicity
+const SCAN: usize = 256;
+
+/// Inverse of a strictly monotonic function
+///
+/// The inverse requires one input upon creation:
+/// * Function: Strictly increasing or strictly decreasing function
+///
+/// The inverse maps an output of the function back to the x that produces
+/// it, so its limits are the range of the function. Every lookup finds the
+/// table cell the value falls in and solves for x inside of that cell with
+/// Brent's method. The table always holds the breakpoints of the function,
+/// and the table constructor adds evenly spaced points so each solve only
+/// has to work across a small cell.
+///
+/// A jump of the function leaves a gap in its range, values inside of the gap
+/// map onto the x of the jump.
+pub struct Inverse {
+  // Function to invert
+  fcn: Box<dyn FunctionOutput>,
+  // (y, x) pairs sorted by y, both sides of every jump are included
+  table: Vec<(f64, f64)>,
+  // Range of the function
+  limits: (f64, f64),
+}
+
+/// Constructors for the Inverse
+impl Inverse {
+  /// Creates the inverse of a function, with a table of its breakpoints
+  ///
+  /// Returns NotMonotonic when the function changes direction or is flat
+  /// anywhere inside of its limits.
+  pub fn new(fcn: Box<dyn FunctionOutput>) -> Result<Inverse, Error> {
+    Inverse::with_table(fcn, 0)
+  }
+  /// Creates the inverse of a function with extra evenly spaced table points
+  ///
+  /// A larger table costs memory and setup time but makes every lookup
+  /// cheaper.
+  pub fn with_table(
+    fcn: Box<dyn FunctionOutput>,
+    size: usize,
+  ) -> Result<Inverse, Error> {
+    check_monotonic(fcn.as_ref())?;
+    let (start, end) = fcn.get_limits();
+    let mut xs = fcn.breakpoints();
+    xs.extend((1..size).map(|k| start + (end - start) * k as f64 / size as f64));
+    xs.sort_by(|a, b| a.total_cmp(b));
+    xs.dedup();
+
+    let mut table = Vec::with_capacity(2 * xs.len());
+    for (idx, x) in xs.iter().enumerate() {
+      // Left limit at every breakpoint after the first so jumps stay separate
+      if idx > 0 {
+        let left = next_down(*x);
+        table.push((value(fcn.as_ref(), left), left));
+      }
+      if *x < end {
+        table.push((value(fcn.as_ref(), *x), *x));
+      }
+    }
+    table.dedup_by(|a, b| a.1 == b.1);
+    // Decreasing functions are reversed and rounding at breakpoints is evened out
+    table.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let limits = (table[0].0, table[table.len() - 1].0);
+    Ok(Inverse { fcn, table, limits })
+  }
+}
+
+/// Output of the function, gaps count as zero like they do in a Function
+fn value(
+  fcn: &dyn FunctionOutput,
+  x: f64,
+) -> f64 {
+  fcn.generate(x).unwrap_or(0.0)
+}
+
+/// Makes sure the function is strictly increasing or strictly decreasing
+///
+/// The velocity must never have the opposite sign, which is exact for
+/// functions with pieces, and the output is also sampled so flat stretches
+/// and jumps in the wrong direction are caught.
+fn check_monotonic(fcn: &dyn FunctionOutput) -> Result<(), Error> {
+  let breakpoints = fcn.breakpoints();
+  let mut samples: Vec<(f64, f64)> = Vec::new();
+  for window in breakpoints.windows(2) {
+    let (a, b) = (window[0], window[1]);
+    if b <= a {
+      continue;
+    }
+    for step in 0..SCAN {
+      let x = a + (b - a) * step as f64 / SCAN as f64;
+      samples.push((x, value(fcn, x)));
+    }
+    samples.push((next_down(b), value(fcn, next_down(b))));
+  }
+  let (first, last) = match (samples.first(), samples.last()) {
+    (Some(first), Some(last)) if samples.len() > 1 => (first.1, last.1),
+    _ => return Err(Error::NotMonotonic(fcn.get_limits().0)),
+  };
+  let direction = if last > first { 1.0 } else { -1.0 };
+  let rounding = 1e-12 * (last - first).abs();
+  for w in samples.windows(2) {
+    let step = direction * (w[1].1 - w[0].1);
+    // Across a breakpoint the left limit and the value may round either way
+    let across = next_up(w[0].0) == w[1].0;
+    if (step <= 0.0 && !across) || (step < -rounding) {
+      return Err(Error::NotMonotonic(w[0].0));
+    }
+  }
+  if let Some(velocity) = extrema::extrema(fcn, Order::Velocity) {
+    let wrong = if direction > 0.0 { velocity.minimum } else { velocity.maximum };
+    // Finite differences are never exactly zero at a flat turning point
+    let tol = 1e-9 * (last - first).abs() / (fcn.get_limits().1 - fcn.get_limits().0);
+    if direction * wrong.value < -tol {
+      return Err(Error::NotMonotonic(wrong.x));
+    }
+  }
+  Ok(())
+}
+
+impl FunctionOutput for Inverse {
+  fn get_limits(&self) -> (f64,f64) {
+    self.limits
+  }
+  fn generate(
+    &self,
+    y: f64,
+  ) -> Option<f64> {
+    if (y < self.limits.0) || (y >= self.limits.1) {
+      return None;
+    }
+    let k = self.table.partition_point(|e| e.0 <= y);
+    let (y0, x0) = self.table[k - 1];
+    let (_, x1) = self.table[k];
+    if y == y0 {
+      Some(x0)
+    } else if next_down(x0.max(x1)) == x0.min(x1) {
+      // Inside of the gap left by a jump
+      Some(x0.max(x1))
+    } else {
+      Some(brent(&|x: f64| value(self.fcn.as_ref(), x) - y, x0, x1))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  #[test]
+  fn increasing() {
+    let factory = Factory;
+    let a = Inverse::new(factory.polynomial(2.0, (0.0, 2.0), vec![1.0, 0.0, 1.0], false)).unwrap();
+    assert_eq!(1.0, a.get_limits().0);
+    assert!((a.get_limits().1 - 5.0).abs() < 1e-14);
+    assert_eq!(Some(0.0), a.generate(1.0));
+    assert!((a.generate(2.0).unwrap() - 1.0).abs() < 1e-15);
+    assert!((a.generate(3.0).unwrap() - 2.0f64.sqrt()).abs() < 1e-15);
+    assert_eq!(None, a.generate(0.5));
+    assert_eq!(None, a.generate(5.0));
+  }
+  #[test]
+  fn decreasing() {
+    let factory = Factory;
+    let a = Inverse::with_table(factory.closure(1.0, (0.0, 1.0), |x| (-x).exp()), 32).unwrap();
+    assert!((a.get_limits().0 - (-1.0f64).exp()).abs() < 1e-15);
+    assert_eq!(1.0, a.get_limits().1);
+    assert!((a.generate(0.5).unwrap() - 2.0f64.ln()).abs() < 1e-15);
+  }
+  #[test]
+  fn jump() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+    a.add_subfunction(factory.ramp(1.0, (2.0, 3.0)));
+    let b = Inverse::with_table(Box::new(a), 10).unwrap();
+    assert_eq!(Some(0.5), b.generate(0.5));
+    assert_eq!(Some(1.0), b.generate(1.5));
+    assert_eq!(Some(1.0), b.generate(2.0));
+    assert_eq!(Some(1.5), b.generate(2.5));
+  }
+  #[test]
+  fn not_monotonic() {
+    let factory = Factory;
+    let a = Inverse::new(factory.fourier(1.0, 1.0, 0.0, vec![], vec![1.0]));
+    assert!(matches!(a, Err(Error::NotMonotonic(_))));
+    // Flat stretch in the middle
+    let mut b = Function::new();
+    b.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+    b.add_subfunction(factory.constant(1.0, 1.0));
+    b.add_subfunction(factory.ramp(1.0, (1.0, 2.0)));
+    assert!(matches!(Inverse::new(Box::new(b)), Err(Error::NotMonotonic(x)) if (1.0..2.0).contains(&x)));
+    // Jump in the wrong direction
+    let mut c = Function::new();
+    c.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+    c.add_subfunction(factory.ramp(1.0, (0.5, 2.0)));
+    assert!(matches!(Inverse::new(Box::new(c)), Err(Error::NotMonotonic(_))));
+  }
+}
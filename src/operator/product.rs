@@ -0,0 +1,92 @@
+// Make the standard subfunction interface available
+use crate::factory::interface::FunctionOutput;
+
+/// Product of two functions
+///
+/// The product requires two inputs upon creation:
+/// * Left: First function, e.g. an envelope
+/// * Right: Second function, e.g. a carrier
+///
+/// Both functions are given the same x so delays inside of either one are
+/// respected. The product is only defined where both of them are defined so
+/// its limits are the overlap of their limits.
+pub struct Product {
+  // First factor
+  lhs: Box<dyn FunctionOutput>,
+  // Second factor
+  rhs: Box<dyn FunctionOutput>,
+  // Overlap of the limits of both factors
+  limits: (f64, f64),
+}
+
+/// Constructor for the Product
+impl Product {
+  /// Creates the product of two functions
+  pub fn new(
+    lhs: Box<dyn FunctionOutput>,
+    rhs: Box<dyn FunctionOutput>,
+  ) -> Product {
+    let (a, b) = (lhs.get_limits(), rhs.get_limits());
+    let start = a.0.max(b.0);
+    // Disjoint limits collapse to an empty range at the later start
+    let limits = (start, a.1.min(b.1).max(start));
+    Product { lhs, rhs, limits }
+  }
+}
+
+impl FunctionOutput for Product {
+  fn get_limits(&self) -> (f64,f64) {
+    self.limits
+  }
+  fn generate(
+    &self,
+    x: f64,
+  ) -> Option<f64> {
+    Some(self.lhs.generate(x)? * self.rhs.generate(x)?)
+  }
+  fn breakpoints(&self) -> Vec<f64> {
+    let mut out = vec![self.limits.0, self.limits.1];
+    out.extend(self.lhs.breakpoints());
+    out.extend(self.rhs.breakpoints());
+    out.retain(|x| (*x >= self.limits.0) && (*x <= self.limits.1));
+    out.sort_by(|a, b| a.total_cmp(b));
+    out.dedup();
+    out
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  #[test]
+  fn product() {
+    let factory = Factory;
+    let a = Product::new(factory.constant(2.0, 3.0), factory.ramp(2.0, (0.0, 2.0)));
+    assert_eq!((0.0, 2.0), a.get_limits());
+    assert_eq!(Some(0.0), a.generate(0.0));
+    assert_eq!(Some(3.0), a.generate(1.0));
+  }
+  #[test]
+  fn overlap() {
+    let factory = Factory;
+    let mut b = Function::new_delay(1.0);
+    b.add_subfunction(factory.constant(3.0, 2.0));
+    let a = Product::new(factory.constant(2.0, 3.0), Box::new(b));
+    assert_eq!((1.0, 2.0), a.get_limits());
+    assert_eq!(vec![1.0, 2.0], a.breakpoints());
+    assert_eq!(None, a.generate(0.5));
+    assert_eq!(Some(6.0), a.generate(1.5));
+    assert_eq!(None, a.generate(2.5));
+  }
+  #[test]
+  fn disjoint() {
+    let factory = Factory;
+    let mut b = Function::new_delay(5.0);
+    b.add_subfunction(factory.constant(1.0, 2.0));
+    let a = Product::new(factory.constant(2.0, 3.0), Box::new(b));
+    assert_eq!((5.0, 5.0), a.get_limits());
+  }
+}
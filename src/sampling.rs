@@ -0,0 +1,8 @@
+/// Adaptive sampling that follows the shape of any function
+pub mod adaptive;
+
+/// Lazy sampling of any function at a fixed rate or given values of x
+pub mod stream;
+
+/// Resampling, decimation and interpolation with an anti-alias filter
+pub mod resample;
@@ -0,0 +1,223 @@
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use crate::numeric::float::next_down;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+/// Cells that every breakpoint interval starts out split into
+const INITIAL: usize = 8;
+
+/// Times that a cell may be halved, the smallest cell is 2^-MAX_DEPTH of the first
+const MAX_DEPTH: u32 = 30;
+
+/// Points that follow the function to within the tolerance
+///
+/// See [`adaptive_over`].
+pub fn adaptive(
+  fcn: &dyn FunctionOutput,
+  tolerance: f64,
+) -> Result<Vec<(f64, f64)>, Error> {
+  adaptive_over(fcn, fcn.get_limits(), tolerance)
+}
+
+/// Points over the range [start,end] that follow the function to within the tolerance
+///
+/// Straight lines between the returned points stay within about the
+/// tolerance of the function, so flat regions get few points and sharply
+/// bending regions get many. Every cell is checked at its midpoint and
+/// quarter points and halved until their distance from the chord is within
+/// the tolerance, which for smooth functions is about the curvature times the
+/// cell width squared over eight. Between the checked points the error may
+/// be slightly larger and features narrower than a checked cell can be
+/// missed entirely.
+///
+/// Every breakpoint inside of the range is a point. A breakpoint where the
+/// function jumps is given twice, first with the left limit and then with
+/// the value, so the jump is drawn as a vertical line. The end of the range
+/// is given with its left limit. The points are sorted by x and points
+/// where the function has no value are left out. It is an error for the
+/// tolerance not to be positive.
+pub fn adaptive_over(
+  fcn: &dyn FunctionOutput,
+  range: (f64, f64),
+  tolerance: f64,
+) -> Result<Vec<(f64, f64)>, Error> {
+  if tolerance.is_nan() || (tolerance <= 0.0) {
+    return Err(Error::InvalidInput("tolerance must be positive"));
+  }
+  let value = |x: f64| fcn.generate(x).unwrap_or(f64::NAN);
+
+  let mut edges = vec![range.0, range.1];
+  edges.extend(fcn.breakpoints());
+  edges.retain(|x| (*x >= range.0) && (*x <= range.1));
+  edges.sort_by(|a, b| a.total_cmp(b));
+  edges.dedup();
+
+  let mut out = Vec::new();
+  for window in edges.windows(2) {
+    let (a, b) = (window[0], window[1]);
+    let width = (b - a) / INITIAL as f64;
+    let mut left = (a, value(a));
+    for k in 1..=INITIAL {
+      let right = if k == INITIAL {
+        // Left limit at the end of the interval
+        (b, value(next_down(b)))
+      } else {
+        let x = a + width * k as f64;
+        (x, value(x))
+      };
+      let x = 0.5 * (left.0 + right.0);
+      refine(&value, left, (x, value(x)), right, tolerance, 0, &mut out);
+      left = right;
+    }
+    push(&mut out, left);
+  }
+  Ok(out)
+}
+
+/// Adds the points of the cell from left up to but not including right
+fn refine(
+  value: &dyn Fn(f64) -> f64,
+  left: (f64, f64),
+  mid: (f64, f64),
+  right: (f64, f64),
+  tolerance: f64,
+  depth: u32,
+  out: &mut Vec<(f64, f64)>,
+) {
+  let x = 0.5 * (left.0 + mid.0);
+  let first = (x, value(x));
+  let x = 0.5 * (mid.0 + right.0);
+  let third = (x, value(x));
+
+  // Distance of a point from the chord, NaN never asks for more points
+  let chord = |p: (f64, f64)| {
+    let t = (p.0 - left.0) / (right.0 - left.0);
+    (p.1 - (left.1 + t * (right.1 - left.1))).abs()
+  };
+  let error = chord(first).max(chord(mid)).max(chord(third));
+  if (error > tolerance) && (depth < MAX_DEPTH) {
+    refine(value, left, first, mid, tolerance, depth + 1, out);
+    refine(value, mid, third, right, tolerance, depth + 1, out);
+  } else {
+    push(out, left);
+  }
+}
+
+/// Adds a point unless it has no value or repeats the last point
+fn push(
+  out: &mut Vec<(f64, f64)>,
+  point: (f64, f64),
+) {
+  if !point.1.is_nan() && (out.last() != Some(&point)) {
+    out.push(point);
+  }
+}
+
+/// Utility for writing sampled points to a file
+///
+/// Every line is x,y.
+pub fn write_to_file(
+  points: &[(f64, f64)],
+  file_name: String,
+) {
+  let path = Path::new(&file_name);
+  let display = path.display();
+
+  let mut file = match File::create(path) {
+    Err(why) => panic!("Couldn't create {}: {}", display, why),
+    Ok(file) => file,
+  };
+
+  for point in points.iter() {
+    if let Err(why) = file.write_all(format!("{},{}\n", point.0, point.1).as_bytes()) {
+      panic!("Couldn't write to {}: {}", display, why);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  /// Largest distance between the function and straight lines through the points
+  fn deviation(
+    fcn: &dyn FunctionOutput,
+    points: &[(f64, f64)],
+  ) -> f64 {
+    let mut worst: f64 = 0.0;
+    for w in points.windows(2) {
+      for k in 1..100 {
+        let t = k as f64 / 100.0;
+        let x = w[0].0 + t * (w[1].0 - w[0].0);
+        worst = worst.max((fcn.generate(x).unwrap() - (w[0].1 + t * (w[1].1 - w[0].1))).abs());
+      }
+    }
+    worst
+  }
+
+  #[test]
+  fn line() {
+    // Nothing to refine so only the first cells are left
+    let factory = Factory;
+    let a = factory.ramp(2.0, (0.0, 4.0));
+    let points = adaptive(a.as_ref(), 1e-6).unwrap();
+    assert_eq!(INITIAL + 1, points.len());
+    assert_eq!((0.0, 0.0), points[0]);
+    assert_eq!(2.0, points[INITIAL].0);
+    assert!((points[INITIAL].1 - 4.0).abs() < 1e-12);
+  }
+  #[test]
+  fn sine() {
+    let factory = Factory;
+    let a = factory.closure(10.0, (0.0, 10.0), |x| x.sin());
+    for tolerance in [1e-2, 1e-4, 1e-6].iter() {
+      let points = adaptive(a.as_ref(), *tolerance).unwrap();
+      assert!(points.windows(2).all(|w| w[0].0 < w[1].0));
+      assert!(deviation(a.as_ref(), &points) <= 1.1 * tolerance);
+    }
+    // A hundred times less error takes about ten times more points
+    let coarse = adaptive(a.as_ref(), 1e-4).unwrap().len() as f64;
+    let fine = adaptive(a.as_ref(), 1e-6).unwrap().len() as f64;
+    assert!((fine / coarse - 10.0).abs() < 2.0);
+  }
+  #[test]
+  fn bump() {
+    // The flat shoulders get far fewer points than the bump itself
+    let factory = Factory;
+    let a = factory.bump(6.0, (-3.0, 3.0), 1.0, 0.0);
+    let points = adaptive(a.as_ref(), 1e-4).unwrap();
+    assert!(deviation(a.as_ref(), &points) <= 1.1e-4);
+    let inside = points.iter().filter(|p| (p.0 > 2.0) && (p.0 < 4.0)).count();
+    assert!(inside > 4 * (points.len() - inside));
+  }
+  #[test]
+  fn jump() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.constant(1.0, 0.0));
+    a.add_subfunction(factory.constant(1.0, 5.0));
+    let points = adaptive(&a, 1e-3).unwrap();
+    assert!(points.contains(&(1.0, 0.0)));
+    assert!(points.contains(&(1.0, 5.0)));
+    assert_eq!(Some(&(2.0, 5.0)), points.last());
+  }
+  #[test]
+  fn range() {
+    let factory = Factory;
+    let a = factory.closure(10.0, (0.0, 10.0), |x| x * x);
+    let points = adaptive_over(a.as_ref(), (2.0, 3.0), 1e-3).unwrap();
+    assert_eq!((2.0, 4.0), points[0]);
+    assert_eq!(3.0, points[points.len() - 1].0);
+  }
+  #[test]
+  fn invalid() {
+    let factory = Factory;
+    let a = factory.ramp(1.0, (0.0, 1.0));
+    assert!(matches!(adaptive(a.as_ref(), 0.0).err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(adaptive(a.as_ref(), f64::NAN).err(), Some(Error::InvalidInput(_))));
+  }
+}
@@ -0,0 +1,194 @@
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+use crate::sampling::stream::{Missing, Sample};
+use std::f64::consts::PI;
+
+/// Zero crossings of the sinc on either side of the centre of the filter
+const ZEROS: usize = 32;
+
+/// Cutoff of the filter as a fraction of the lower Nyquist frequency
+///
+/// The Blackman window spreads the edge of the filter over about 17% of the
+/// Nyquist frequency with 32 zero crossings, centred on the cutoff. At 0.9
+/// the response is flat to 0.01 dB up to 0.8 of the Nyquist frequency, down
+/// 6 dB at the cutoff and down at least 75 dB from the Nyquist frequency up.
+const CUTOFF: f64 = 0.9;
+
+/// Resamples a buffer by the rational factor up / down
+///
+/// The output rate is the input rate times up over down, and output sample
+/// n is at the same time as input sample n * down / up. Conceptually the
+/// buffer is upsampled by inserting up - 1 zeros between samples, low-pass
+/// filtered and then every down-th sample is kept, which is computed
+/// directly without building the upsampled buffer. The filter is a
+/// Blackman windowed sinc whose cutoff sits at 0.9 of the lower of the two
+/// Nyquist frequencies, so content that the output rate cannot represent is
+/// removed instead of aliasing and the images made by upsampling are
+/// removed as well.
+///
+/// The weights used for every output sample are normalized to sum to one,
+/// so a constant stays exactly constant. Past both ends of the buffer the
+/// first and last samples are repeated. There are ceil(len * up / down)
+/// output samples. It is an error for either factor to be zero.
+pub fn resample(
+  samples: &[f64],
+  up: usize,
+  down: usize,
+) -> Result<Vec<f64>, Error> {
+  if (up == 0) || (down == 0) {
+    return Err(Error::InvalidInput("resampling factors must be positive"));
+  }
+  if samples.is_empty() {
+    return Ok(Vec::new());
+  }
+  let widest = up.max(down);
+  let half = (ZEROS * widest) as i64;
+  let cutoff = CUTOFF * 0.5 / widest as f64;
+  let (up, down) = (up as i64, down as i64);
+  let last = samples.len() as i64 - 1;
+
+  let count = (samples.len() as i64 * up + down - 1) / down;
+  let out = (0..count)
+    .map(|n| {
+      // Position in the upsampled buffer and the input samples within reach of the filter
+      let m = n * down;
+      let first = (m - half).div_euclid(up) + if (m - half).rem_euclid(up) == 0 { 0 } else { 1 };
+      let end = (m + half).div_euclid(up);
+      let mut sum = 0.0;
+      let mut weights = 0.0;
+      for j in first..=end {
+        let weight = tap((m - j * up) as f64, half as f64, cutoff);
+        sum += weight * samples[j.clamp(0, last) as usize];
+        weights += weight;
+      }
+      sum / weights
+    })
+    .collect();
+  Ok(out)
+}
+
+/// Keeps one in every factor samples after removing what would alias
+///
+/// See [`resample`].
+pub fn decimate(
+  samples: &[f64],
+  factor: usize,
+) -> Result<Vec<f64>, Error> {
+  resample(samples, 1, factor)
+}
+
+/// Makes factor samples for every sample and removes the images in between
+///
+/// See [`resample`].
+pub fn interpolate(
+  samples: &[f64],
+  factor: usize,
+) -> Result<Vec<f64>, Error> {
+  resample(samples, factor, 1)
+}
+
+/// Samples a function at a rate and resamples it by the rational factor up / down
+///
+/// The function is sampled like write_to_file with missing values counted as
+/// zero, see [`resample`]. Sampling the function directly at the lower rate
+/// would alias everything above the new Nyquist frequency into the output.
+/// It is an error for the rate not to be positive or either factor to be
+/// zero.
+pub fn resample_function(
+  fcn: &dyn FunctionOutput,
+  sample_rate_hz: f64,
+  up: usize,
+  down: usize,
+) -> Result<Vec<f64>, Error> {
+  let samples: Vec<f64> = fcn.samples(sample_rate_hz)?.missing(Missing::Fill(0.0)).map(|s| s.1).collect();
+  resample(&samples, up, down)
+}
+
+/// Blackman windowed sinc at distance k from the centre of the filter
+fn tap(
+  k: f64,
+  half: f64,
+  cutoff: f64,
+) -> f64 {
+  let sinc = if k == 0.0 { 2.0 * cutoff } else { (2.0 * PI * cutoff * k).sin() / (PI * k) };
+  let phase = PI * k / half;
+  sinc * (0.42 + 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+
+  /// Samples of cos(2 pi f n / rate)
+  fn tone(
+    frequency: f64,
+    rate: f64,
+    len: usize,
+  ) -> Vec<f64> {
+    (0..len).map(|n| (2.0 * PI * frequency * n as f64 / rate).cos()).collect()
+  }
+  /// Largest magnitude away from the ends where the repeated edges distort
+  fn peak(samples: &[f64]) -> f64 {
+    let skip = samples.len() / 4;
+    samples[skip..samples.len() - skip].iter().fold(0.0, |a, b| a.max(b.abs()))
+  }
+
+  #[test]
+  fn constant() {
+    let samples = vec![2.5; 37];
+    for (up, down) in [(1, 3), (4, 1), (3, 2), (2, 5)].iter() {
+      let out = resample(&samples, *up, *down).unwrap();
+      assert_eq!((37 * up).div_ceil(*down), out.len());
+      assert!(out.iter().all(|v| (v - 2.5).abs() < 1e-12));
+    }
+  }
+  #[test]
+  fn decimation() {
+    // Decimating 1 kHz by 4 keeps 50 Hz and removes 200 Hz, which would alias to 50 Hz
+    let low = decimate(&tone(50.0, 1000.0, 2000), 4).unwrap();
+    let high = decimate(&tone(200.0, 1000.0, 2000), 4).unwrap();
+    assert_eq!(500, low.len());
+    assert!((peak(&low) - 1.0).abs() < 1e-3);
+    assert!(peak(&high) < 1e-3);
+    let expected = tone(50.0, 250.0, 500);
+    assert!(low[125..375].iter().zip(expected[125..375].iter()).all(|(a, b)| (a - b).abs() < 1e-3));
+  }
+  #[test]
+  fn nyquist() {
+    // At and just past the new Nyquist frequency of 125 Hz nothing may alias back
+    for frequency in [125.0, 127.5].iter() {
+      let out = decimate(&tone(*frequency, 1000.0, 4000), 4).unwrap();
+      assert!(peak(&out) < 1e-3);
+    }
+  }
+  #[test]
+  fn zero_factor() {
+    let samples = vec![1.0; 8];
+    assert!(matches!(decimate(&samples, 0).err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(resample(&samples, 0, 2).err(), Some(Error::InvalidInput(_))));
+  }
+  #[test]
+  fn interpolation() {
+    // Every input sample is kept and the tone is filled in between
+    let out = interpolate(&tone(10.0, 100.0, 400), 3).unwrap();
+    let expected = tone(10.0, 300.0, 1200);
+    assert_eq!(1200, out.len());
+    assert!(out[300..900].iter().zip(expected[300..900].iter()).all(|(a, b)| (a - b).abs() < 1e-3));
+  }
+  #[test]
+  fn rational() {
+    let out = resample(&tone(20.0, 300.0, 900), 2, 3).unwrap();
+    let expected = tone(20.0, 200.0, 600);
+    assert_eq!(600, out.len());
+    assert!(out[150..450].iter().zip(expected[150..450].iter()).all(|(a, b)| (a - b).abs() < 1e-3));
+  }
+  #[test]
+  fn function() {
+    let factory = Factory;
+    let a = factory.closure(2.0, (0.0, 2.0), |x| (2.0 * PI * 5.0 * x).sin());
+    let out = resample_function(a.as_ref(), 1000.0, 1, 10).unwrap();
+    assert_eq!(200, out.len());
+    assert!((out[50] - (2.0 * PI * 5.0 * 0.5).sin()).abs() < 1e-3);
+  }
+}
@@ -0,0 +1,334 @@
+use crate::error::Error;
+use crate::factory::interface::FunctionOutput;
+
+/// What to do with a sample where the function has no value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Missing {
+  /// Leave the sample out
+  Skip,
+  /// Use this value instead
+  Fill(f64),
+  /// Repeat the last value, samples before the first value are left out
+  Hold,
+  /// End the stream
+  Stop,
+}
+
+/// Lazy sampling of any function
+///
+/// Sampling borrows the function and generates one (time, value) pair at a
+/// time so nothing is allocated up front, which suits streaming to hardware
+/// in real time. It is implemented for every function and for boxed
+/// functions so `fcn.samples(rate)` works on both.
+pub trait Sample {
+  /// Samples at a fixed rate over the limits
+  ///
+  /// See [`Samples`], it is an error for the rate not to be positive.
+  fn samples(
+    &self,
+    sample_rate_hz: f64,
+  ) -> Result<Samples<'_>, Error>;
+  /// Samples at the given values of x in their order
+  ///
+  /// See [`SamplesAt`].
+  fn samples_at<I: IntoIterator<Item = f64>>(
+    &self,
+    xs: I,
+  ) -> SamplesAt<'_, I::IntoIter>;
+}
+
+impl<T: FunctionOutput> Sample for T {
+  fn samples(
+    &self,
+    sample_rate_hz: f64,
+  ) -> Result<Samples<'_>, Error> {
+    Samples::new(self, sample_rate_hz)
+  }
+  fn samples_at<I: IntoIterator<Item = f64>>(
+    &self,
+    xs: I,
+  ) -> SamplesAt<'_, I::IntoIter> {
+    SamplesAt::new(self, xs.into_iter())
+  }
+}
+
+impl<'b> Sample for dyn FunctionOutput + 'b {
+  fn samples(
+    &self,
+    sample_rate_hz: f64,
+  ) -> Result<Samples<'_>, Error> {
+    Samples::new(self, sample_rate_hz)
+  }
+  fn samples_at<I: IntoIterator<Item = f64>>(
+    &self,
+    xs: I,
+  ) -> SamplesAt<'_, I::IntoIter> {
+    SamplesAt::new(self, xs.into_iter())
+  }
+}
+
+/// Applies the policy for missing values to the values as they come
+#[derive(Clone, Debug)]
+struct Policy {
+  // What to do with missing values
+  missing: Missing,
+  // Last value that was given out
+  last: Option<f64>,
+  // Whether a missing value ended the stream
+  stopped: bool,
+}
+
+impl Policy {
+  /// Value to give out, None leaves the sample out
+  fn resolve(
+    &mut self,
+    value: Option<f64>,
+  ) -> Option<f64> {
+    let out = match (value, self.missing) {
+      (Some(value), _) => Some(value),
+      (None, Missing::Skip) => None,
+      (None, Missing::Fill(value)) => Some(value),
+      (None, Missing::Hold) => self.last,
+      (None, Missing::Stop) => {
+        self.stopped = true;
+        None
+      }
+    };
+    if out.is_some() {
+      self.last = out;
+    }
+    out
+  }
+}
+
+/// Iterator over a function sampled at a fixed rate
+///
+/// The samples start at the start of the limits and there are
+/// floor(width * rate) of them so the end of the limits, where the function
+/// has no value, is never reached. Sample n is at start + n / rate, which is
+/// computed from the integer step every time so the timing does not drift
+/// the way adding up 1 / rate would. The start, the end and what to do with
+/// missing values can be changed before iterating, missing values are
+/// skipped unless told otherwise.
+#[derive(Clone)]
+pub struct Samples<'a> {
+  // Function being sampled
+  fcn: &'a dyn FunctionOutput,
+  // Samples per unit of x
+  rate: f64,
+  // x of the first sample
+  start: f64,
+  // Samples stay below this x
+  end: f64,
+  // Next sample to generate
+  step: u64,
+  // Number of samples
+  count: u64,
+  // What to do with missing values
+  policy: Policy,
+}
+
+/// Constructor for the Samples
+impl<'a> Samples<'a> {
+  /// Creates the samples of a function over its limits
+  pub fn new(
+    fcn: &'a dyn FunctionOutput,
+    sample_rate_hz: f64,
+  ) -> Result<Samples<'a>, Error> {
+    if sample_rate_hz <= 0.0 {
+      return Err(Error::InvalidInput("sample rate must be positive"));
+    }
+    let limits = fcn.get_limits();
+    Ok(Samples {
+      fcn,
+      rate: sample_rate_hz,
+      start: limits.0,
+      end: limits.1,
+      step: 0,
+      count: 0,
+      policy: Policy { missing: Missing::Skip, last: None, stopped: false },
+    }
+    .counted())
+  }
+  /// Starts sampling at x instead of the start of the limits
+  pub fn start(
+    mut self,
+    x: f64,
+  ) -> Samples<'a> {
+    self.start = x;
+    self.counted()
+  }
+  /// Stops sampling before x instead of the end of the limits
+  pub fn end(
+    mut self,
+    x: f64,
+  ) -> Samples<'a> {
+    self.end = x;
+    self.counted()
+  }
+  /// Sets what to do with missing values
+  pub fn missing(
+    mut self,
+    missing: Missing,
+  ) -> Samples<'a> {
+    self.policy.missing = missing;
+    self
+  }
+  /// Updates the number of samples after the start or end changed
+  fn counted(mut self) -> Samples<'a> {
+    // Use floor because a value >= to the exact end is out of range and
+    // floor is the safer bet. Negative widths saturate to zero samples.
+    self.count = ((self.end - self.start) * self.rate).floor() as u64;
+    self
+  }
+}
+
+impl<'a> Iterator for Samples<'a> {
+  type Item = (f64, f64);
+  fn next(&mut self) -> Option<(f64, f64)> {
+    while (self.step < self.count) && !self.policy.stopped {
+      let time = self.step as f64 / self.rate + self.start;
+      self.step += 1;
+      if let Some(value) = self.policy.resolve(self.fcn.generate(time)) {
+        return Some((time, value));
+      }
+    }
+    None
+  }
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let remaining = if self.policy.stopped { 0 } else { (self.count - self.step) as usize };
+    match self.policy.missing {
+      Missing::Fill(_) => (remaining, Some(remaining)),
+      _ => (0, Some(remaining)),
+    }
+  }
+}
+
+/// Iterator over a function sampled at explicit values of x
+///
+/// The values of x come from another iterator and can be in any order or
+/// spacing, for example the timestamps of a clock that jitters. Missing
+/// values are skipped unless told otherwise.
+pub struct SamplesAt<'a, I> {
+  // Function being sampled
+  fcn: &'a dyn FunctionOutput,
+  // Where to sample next
+  xs: I,
+  // What to do with missing values
+  policy: Policy,
+}
+
+/// Constructor for the SamplesAt
+impl<'a, I: Iterator<Item = f64>> SamplesAt<'a, I> {
+  /// Creates the samples of a function at the values of x
+  pub fn new(
+    fcn: &'a dyn FunctionOutput,
+    xs: I,
+  ) -> SamplesAt<'a, I> {
+    SamplesAt {
+      fcn,
+      xs,
+      policy: Policy { missing: Missing::Skip, last: None, stopped: false },
+    }
+  }
+  /// Sets what to do with missing values
+  pub fn missing(
+    mut self,
+    missing: Missing,
+  ) -> SamplesAt<'a, I> {
+    self.policy.missing = missing;
+    self
+  }
+}
+
+impl<'a, I: Iterator<Item = f64>> Iterator for SamplesAt<'a, I> {
+  type Item = (f64, f64);
+  fn next(&mut self) -> Option<(f64, f64)> {
+    while !self.policy.stopped {
+      let x = self.xs.next()?;
+      if let Some(value) = self.policy.resolve(self.fcn.generate(x)) {
+        return Some((x, value));
+      }
+    }
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::factory::Factory;
+  use crate::function::Function;
+
+  /// Identity over [0,3) without a value over [1,2)
+  struct Gap;
+
+  impl FunctionOutput for Gap {
+    fn get_limits(&self) -> (f64,f64) {
+      (0.0,3.0)
+    }
+    fn generate(
+      &self,
+      x: f64,
+    ) -> Option<f64> {
+      if (1.0..2.0).contains(&x) {
+        None
+      } else {
+        Some(x)
+      }
+    }
+  }
+
+  #[test]
+  fn fixed_rate() {
+    let factory = Factory;
+    let a = factory.ramp(1.0, (0.0, 1.0));
+    let samples: Vec<(f64, f64)> = a.samples(4.0).unwrap().collect();
+    assert_eq!(vec![(0.0, 0.0), (0.25, 0.25), (0.5, 0.5), (0.75, 0.75)], samples);
+    assert_eq!((0, Some(4)), a.samples(4.0).unwrap().size_hint());
+  }
+  #[test]
+  fn no_drift() {
+    // Adding up 0.1 drifts away from the exact multiples, stepping does not
+    let factory = Factory;
+    let a = factory.ramp(1000.0, (0.0, 1.0));
+    let last = a.samples(10.0).unwrap().last().unwrap();
+    assert_eq!(9999.0 / 10.0, last.0);
+    assert_eq!(10000, a.samples(10.0).unwrap().count());
+  }
+  #[test]
+  fn overrides() {
+    let factory = Factory;
+    let mut a = Function::new();
+    a.add_subfunction(factory.ramp(4.0, (0.0, 4.0)));
+    let xs: Vec<f64> = a.samples(2.0).unwrap().start(1.0).end(2.5).map(|s| s.0).collect();
+    assert_eq!(vec![1.0, 1.5, 2.0], xs);
+    assert_eq!(0, a.samples(2.0).unwrap().start(3.0).end(1.0).count());
+  }
+  #[test]
+  fn missing() {
+    let b: Box<dyn FunctionOutput> = Box::new(Gap);
+    let values = |missing: Missing| -> Vec<f64> { b.samples(2.0).unwrap().missing(missing).map(|s| s.1).collect() };
+    assert_eq!(vec![0.0, 0.5, 2.0, 2.5], values(Missing::Skip));
+    assert_eq!(vec![0.0, 0.5, -1.0, -1.0, 2.0, 2.5], values(Missing::Fill(-1.0)));
+    assert_eq!(vec![0.0, 0.5, 0.5, 0.5, 2.0, 2.5], values(Missing::Hold));
+    assert_eq!(vec![0.0, 0.5], values(Missing::Stop));
+    assert_eq!((6, Some(6)), b.samples(2.0).unwrap().missing(Missing::Fill(0.0)).size_hint());
+  }
+  #[test]
+  fn invalid_rate() {
+    let factory = Factory;
+    let a = factory.ramp(1.0, (0.0, 1.0));
+    assert!(matches!(a.samples(0.0).err(), Some(Error::InvalidInput(_))));
+    assert!(matches!(a.samples(-4.0).err(), Some(Error::InvalidInput(_))));
+  }
+  #[test]
+  fn timestamps() {
+    let factory = Factory;
+    let a = factory.ramp(1.0, (0.0, 2.0));
+    let samples: Vec<(f64, f64)> = a.samples_at(vec![0.5, 0.1, 2.0, 0.25]).collect();
+    assert_eq!(vec![(0.5, 1.0), (0.1, 0.2), (0.25, 0.5)], samples);
+    let held: Vec<f64> = a.samples_at(vec![0.5, 2.0]).missing(Missing::Hold).map(|s| s.1).collect();
+    assert_eq!(vec![1.0, 1.0], held);
+  }
+}
@@ -0,0 +1,19 @@
+use piecewise_fcn_lib as pw;
+use pw::sampling::adaptive;
+
+/// Helping to understand adaptive sampling
+///
+/// A narrow bump on a long flat baseline followed by a step is sampled to
+/// within a thousandth. The flat parts only get a handful of points, the
+/// bump gets most of them and the step is drawn as a vertical line.
+///
+#[test]
+fn adaptive_bump() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.bump(10.0, (-10.0, 10.0), 1.0, 0.0));
+  fcn1.add_subfunction(factory.step(5.0, 2.5, (0.0, 0.5), 0.5));
+  let points = adaptive::adaptive(&fcn1, 1e-3).unwrap();
+
+  adaptive::write_to_file(&points, "tests/output/adaptive_bump.csv".to_string());
+}
@@ -0,0 +1,45 @@
+use piecewise_fcn_lib as pw;
+use pw::builder::adsr::{self, Curve};
+use pw::operator::product::Product;
+use std::f64::consts::PI;
+
+/// Helping to understand envelope curves
+///
+/// The same envelope is built with linear and exponential segments and
+/// stacked one after another with a delay. The exponential segments move
+/// quickly at first and then settle onto the next level.
+///
+#[test]
+fn adsr_curves() {
+  let mut fcn1 = pw::Function::new();
+  for (idx, curve) in [Curve::Linear, Curve::Exponential(5.0)].iter().enumerate() {
+    let mut env = pw::Function::new_delay(5.0 * idx as f64);
+    env.add_subfunction(Box::new(adsr::adsr((0.5, 1.0, 2.0, 1.0), 0.6, *curve).unwrap()));
+    fcn1.add_function(env);
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/adsr_curves.csv".to_string(),
+  );
+}
+
+/// Helping to understand shaped tones
+///
+/// An exponential envelope is multiplied onto a 10 Hz sine carrier so the
+/// tone swells, settles to the sustain level and dies away.
+///
+#[test]
+fn adsr_tone() {
+  let factory = pw::Factory;
+  let env = adsr::adsr((0.2, 0.3, 1.0, 0.5), 0.5, Curve::Exponential(4.0)).unwrap();
+  let carrier = factory.closure(2.0, (0.0, 2.0), |x| (2.0 * PI * 10.0 * x).sin());
+  let fcn1 = Product::new(Box::new(env), carrier);
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/adsr_tone.csv".to_string(),
+  );
+}
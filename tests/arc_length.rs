@@ -0,0 +1,25 @@
+use piecewise_fcn_lib as pw;
+use pw::operator::arc_length::ArcLength;
+
+/// Helping to understand constant speed traversal
+///
+/// A cubic spline through a few waypoints is reparameterized by its arc
+/// length. Sampling the output evenly moves along the curve at constant
+/// speed, so the steep parts of the spline take longer than the flat parts
+/// instead of being rushed through.
+///
+#[test]
+fn arc_length_spline() {
+  let factory = pw::Factory;
+  let x = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+  let y = vec![0.0, 2.0, 0.5, 1.5, 0.0];
+  let mut path = pw::Function::new();
+  path.add_subfunction(factory.pchip(4.0, x, y).unwrap());
+  let fcn1 = ArcLength::new(Box::new(path));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/arc_length_spline.csv".to_string(),
+  );
+}
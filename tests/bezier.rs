@@ -0,0 +1,45 @@
+use piecewise_fcn_lib as pw;
+
+/// Helping to understand Bézier control points
+///
+/// The control points pull the curve towards them without passing through
+/// anything but the end points. Raising the degree with more control points
+/// gives the designer more handles on the same duration.
+///
+#[test]
+fn bezier_control() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.bezier(1.0, (0.0, 1.0), vec![0.0, 1.0]));
+  fcn1.add_subfunction(factory.bezier(1.0, (0.0, 1.0), vec![1.0, 2.0, 0.0]));
+  fcn1.add_subfunction(factory.bezier(1.0, (0.0, 1.0), vec![0.0, -1.0, 2.0, 1.0]));
+  fcn1.add_subfunction(factory.bezier(1.0, (0.0, 1.0), vec![1.0, 0.0, 2.0, 0.0, 1.0, 0.0]));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/bezier_control.csv".to_string(),
+  );
+}
+
+/// Helping to understand cubic-bezier easing
+///
+/// These are the CSS ease, ease-in, ease-out and ease-in-out curves chained
+/// together, each one moving between 0 and 1. The designer's numbers from the
+/// browser can be pasted straight in.
+///
+#[test]
+fn bezier_easing() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.cubic_bezier(1.0, (0.25, 0.1), (0.25, 1.0), (0.0, 1.0)).unwrap());
+  fcn1.add_subfunction(factory.cubic_bezier(1.0, (0.42, 0.0), (1.0, 1.0), (1.0, 0.0)).unwrap());
+  fcn1.add_subfunction(factory.cubic_bezier(1.0, (0.0, 0.0), (0.58, 1.0), (0.0, 1.0)).unwrap());
+  fcn1.add_subfunction(factory.cubic_bezier(1.0, (0.42, 0.0), (0.58, 1.0), (1.0, 0.0)).unwrap());
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/bezier_easing.csv".to_string(),
+  );
+}
@@ -0,0 +1,72 @@
+use piecewise_fcn_lib as pw;
+use pw::factory::bspline::BSpline;
+
+/// Helping to understand knot vectors
+///
+/// The same control points are used with a clamped and an unclamped knot
+/// vector. The clamped spline starts and ends on the first and last control
+/// point while the unclamped spline only blends them together.
+///
+#[test]
+fn bspline_knots() {
+  let factory = pw::Factory;
+  let control = vec![0.0, 2.0, -1.0, 3.0, 1.0, 0.0];
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.bspline(
+    2.0,
+    3,
+    vec![0.0, 0.0, 0.0, 0.0, 0.3, 0.6, 1.0, 1.0, 1.0, 1.0],
+    control.clone(),
+  ).unwrap());
+  fcn1.add_subfunction(factory.bspline(
+    2.0,
+    3,
+    vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+    control,
+  ).unwrap());
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/bspline_knots.csv".to_string(),
+  );
+}
+
+/// Helping to understand B-spline conversion
+///
+/// A toolpath spline is split into one Polynomial per knot span and the
+/// resulting Function is written out instead of the spline itself. The file
+/// matches the spline exactly without resampling it into tiny segments.
+///
+#[test]
+fn bspline_to_function() {
+  let spline = BSpline::clamped(4.0, 3, vec![0.0, 1.0, 3.0, 2.0, 2.5, 0.0, 1.0]).unwrap();
+  let fcn1 = spline.to_function();
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/bspline_to_function.csv".to_string(),
+  );
+}
+
+/// Helping to understand NURBS weights
+///
+/// Raising the weight of the middle control point pulls the curve towards it
+/// without changing the knot vector or any of the other points.
+///
+#[test]
+fn nurbs_weights() {
+  let factory = pw::Factory;
+  let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+  let mut fcn1 = pw::Function::new();
+  for weight in [0.5, 1.0, 2.0, 4.0].iter() {
+    fcn1.add_subfunction(factory.nurbs(1.0, 2, knots.clone(), vec![0.0, 1.0, 0.0], vec![1.0, *weight, 1.0]).unwrap());
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/nurbs_weights.csv".to_string(),
+  );
+}
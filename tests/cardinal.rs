@@ -0,0 +1,49 @@
+use piecewise_fcn_lib as pw;
+use pw::builder::cardinal::{self, Parameterization};
+
+/// Helping to understand spline parameterization
+///
+/// The same waypoints with uneven spacing are run through each parameterization
+/// and stacked one after another with delays. Uniform spacing overshoots
+/// between the close waypoints while centripetal and chordal follow them more
+/// tightly.
+///
+#[test]
+fn cardinal_parameterization() {
+  let x = [0.0, 0.2, 0.4, 2.0, 2.2, 4.0];
+  let y = vec![0.0, 3.0, 0.0, 1.0, 4.0, 0.0];
+  let mut fcn1 = pw::Function::new();
+  let params = [Parameterization::Uniform, Parameterization::Centripetal, Parameterization::Chordal];
+  for (idx, p) in params.iter().enumerate() {
+    let shifted: Vec<f64> = x.iter().map(|v| v + 4.0 * idx as f64).collect();
+    fcn1.add_function(cardinal::catmull_rom(&shifted, &y, *p).unwrap());
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/cardinal_parameterization.csv".to_string(),
+  );
+}
+
+/// Helping to understand tension
+///
+/// Raising the tension shortens the tangents so the curve pulls tighter
+/// towards straight lines between the waypoints.
+///
+#[test]
+fn cardinal_tension() {
+  let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+  let y = vec![0.0, 2.0, 0.0, 2.0, 0.0];
+  let mut fcn1 = pw::Function::new();
+  for (idx, tension) in [0.0, 0.5, 1.0].iter().enumerate() {
+    let shifted: Vec<f64> = x.iter().map(|v| v + 4.0 * idx as f64).collect();
+    fcn1.add_function(cardinal::cardinal(&shifted, &y, *tension, Parameterization::Centripetal).unwrap());
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/cardinal_tension.csv".to_string(),
+  );
+}
@@ -0,0 +1,51 @@
+use piecewise_fcn_lib as pw;
+use pw::factory::chebyshev::Chebyshev;
+
+/// Helping to understand the basis
+///
+/// Each coefficient scales one Chebyshev polynomial T_k(x), so a single
+/// non-zero coefficient at index k draws T_k(x) by itself. Every one of them
+/// stays between -1 and 1 over the interval [-1,1] and wiggles more as the
+/// index grows.
+///
+#[test]
+fn chebyshev_basis() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.chebyshev(1.0, (-1.0, 1.0), vec![1.0], false));
+  fcn1.add_subfunction(factory.chebyshev(1.0, (-1.0, 1.0), vec![0.0, 1.0], false));
+  fcn1.add_subfunction(factory.chebyshev(1.0, (-1.0, 1.0), vec![0.0, 0.0, 1.0], false));
+  fcn1.add_subfunction(factory.chebyshev(1.0, (-1.0, 1.0), vec![0.0, 0.0, 0.0, 1.0], false));
+  fcn1.add_subfunction(factory.chebyshev(1.0, (-1.0, 1.0), vec![0.0, 0.0, 0.0, 0.0, 1.0], false));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/chebyshev_basis.csv".to_string(),
+  );
+}
+
+/// Helping to understand precision
+///
+/// T_40(x) written out in the power basis has coefficients as large as 2e14
+/// with alternating signs, so evaluating it as a Polynomial cancels away most
+/// of the digits near the ends of the interval. The first sub-function is the
+/// Chebyshev series and the second is the exact same curve as a Polynomial,
+/// the difference between them is the rounding error of the power basis.
+///
+#[test]
+fn chebyshev_precision() {
+  let factory = pw::Factory;
+  let mut coeff = vec![0.0; 41];
+  coeff[40] = 1.0;
+  let series = Chebyshev::new(1.0, (-1.0, 1.0), coeff.clone(), false);
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.chebyshev(1.0, (-1.0, 1.0), coeff, false));
+  fcn1.add_subfunction(Box::new(series.to_polynomial()));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/chebyshev_precision.csv".to_string(),
+  );
+}
@@ -0,0 +1,22 @@
+use piecewise_fcn_lib as pw;
+
+/// Helping to understand closures
+///
+/// A closure is evaluated over its interval exactly like a polynomial so the
+/// same sine closure can be stretched or shifted by changing the duration and
+/// interval. This example places a full period of sine next to a half period
+/// that was mapped onto the same duration.
+///
+#[test]
+fn closure_interval() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.closure(1.0, (0.0, 2.0 * std::f64::consts::PI), f64::sin));
+  fcn1.add_subfunction(factory.closure(1.0, (0.0, std::f64::consts::PI), f64::sin));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/closure_interval.csv".to_string(),
+  );
+}
@@ -0,0 +1,28 @@
+use piecewise_fcn_lib as pw;
+use pw::analysis::statistics;
+use pw::operator::convolution::Convolution;
+
+/// Helping to understand input shaping
+///
+/// A trapezoid move is convolved with a bump kernel scaled to unit area, so
+/// the shaped move reaches the same height but its corners are rounded off.
+/// The shaped move lasts as long as the move plus the kernel.
+///
+#[test]
+fn convolution_shaping() {
+  let factory = pw::Factory;
+  let mut profile = pw::Function::new();
+  profile.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+  profile.add_subfunction(factory.constant(2.0, 1.0));
+  profile.add_subfunction(factory.ramp(1.0, (1.0, 0.0)));
+  let kernel = factory.bump(0.5, (-1.0, 1.0), 1.0, 0.0);
+  let area = statistics::statistics(kernel.as_ref()).unwrap().mean * 0.5;
+  let kernel = factory.bump(0.5, (-1.0, 1.0), 1.0 / area, 0.0);
+  let fcn1 = Convolution::new(Box::new(profile), kernel);
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/convolution_shaping.csv".to_string(),
+  );
+}
@@ -0,0 +1,55 @@
+use piecewise_fcn_lib as pw;
+use pw::factory::easing::{Family, Mode};
+
+/// Helping to understand easing families
+///
+/// Every family is chained one after another as an in-out curve from 0 to 1
+/// and back down again, after smoothstep and smootherstep.
+///
+#[test]
+fn easing_families() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.smoothstep(1.0, (0.0, 1.0)));
+  fcn1.add_subfunction(factory.smootherstep(1.0, (1.0, 0.0)));
+  let families = [
+    Family::Quad,
+    Family::Cubic,
+    Family::Quart,
+    Family::Sine,
+    Family::Expo,
+    Family::Circ,
+    Family::Back,
+    Family::Elastic,
+    Family::Bounce,
+  ];
+  for family in families.iter() {
+    fcn1.add_subfunction(factory.easing(1.0, *family, Mode::InOut, (0.0, 1.0)));
+    fcn1.add_subfunction(factory.easing(1.0, *family, Mode::InOut, (1.0, 0.0)));
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/easing_families.csv".to_string(),
+  );
+}
+
+/// Helping to understand easing modes
+///
+/// The bounce family eased in, out and in-out.
+///
+#[test]
+fn easing_modes() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  for mode in [Mode::In, Mode::Out, Mode::InOut].iter() {
+    fcn1.add_subfunction(factory.easing(1.0, Family::Bounce, *mode, (0.0, 1.0)));
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/easing_modes.csv".to_string(),
+  );
+}
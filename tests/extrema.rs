@@ -0,0 +1,34 @@
+use piecewise_fcn_lib as pw;
+use pw::analysis::extrema::{self, Order};
+use pw::operator::derivative::Derivative;
+
+/// Helping to understand velocity limits
+///
+/// A move built from smootherstep segments is differentiated and the peak
+/// velocity is found. Constants at the largest and smallest velocity are
+/// placed after the velocity profile to show the limits the hardware has to
+/// support.
+///
+#[test]
+fn extrema_velocity() {
+  let factory = pw::Factory;
+  let mut profile = pw::Function::new();
+  profile.add_subfunction(factory.smootherstep(1.0, (0.0, 2.0)));
+  profile.add_subfunction(factory.constant(0.5, 2.0));
+  profile.add_subfunction(factory.smootherstep(2.0, (2.0, -1.0)));
+  let velocity = extrema::extrema(&profile, Order::Velocity).unwrap();
+
+  let mut limits = pw::Function::new_delay(3.5);
+  limits.add_subfunction(factory.constant(1.0, velocity.maximum.value));
+  limits.add_subfunction(factory.constant(1.0, velocity.minimum.value));
+
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(Box::new(Derivative::new(Box::new(profile), 1).unwrap()));
+  fcn1.add_function(limits);
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/extrema_velocity.csv".to_string(),
+  );
+}
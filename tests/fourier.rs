@@ -0,0 +1,52 @@
+use piecewise_fcn_lib as pw;
+use pw::analysis::fourier;
+
+/// Helping to understand harmonics
+///
+/// Adding odd sine harmonics of 1/n builds up a square wave. Each subfunction
+/// adds two more harmonics to the one before it.
+///
+#[test]
+fn fourier_harmonics() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  let mut sine = Vec::new();
+  for n in 1..=4 {
+    sine.push(1.0 / (2 * n - 1) as f64);
+    sine.push(0.0);
+    fcn1.add_subfunction(factory.fourier(2.0, 1.0, 0.0, vec![], sine.clone()));
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/fourier_harmonics.csv".to_string(),
+  );
+}
+
+/// Helping to understand Fourier analysis
+///
+/// A trapezoid profile is analysed and rebuilt from its first eight harmonics.
+/// The rebuilt series is placed after the original for comparison.
+///
+#[test]
+fn fourier_analysis() {
+  let factory = pw::Factory;
+  let mut profile = pw::Function::new();
+  profile.add_subfunction(factory.ramp(0.5, (0.0, 1.0)));
+  profile.add_subfunction(factory.constant(1.0, 1.0));
+  profile.add_subfunction(factory.ramp(0.5, (1.0, 0.0)));
+  let series = fourier::coefficients(&profile, 8);
+
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_function(profile);
+  let mut rebuilt = pw::Function::new_delay(2.0);
+  rebuilt.add_subfunction(Box::new(series));
+  fcn1.add_function(rebuilt);
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/fourier_analysis.csv".to_string(),
+  );
+}
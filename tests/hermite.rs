@@ -0,0 +1,44 @@
+use piecewise_fcn_lib as pw;
+
+/// Helping to understand Hermite segments
+///
+/// A move is described the way it is usually specified: ramp up from rest to
+/// a cruise slope, cruise, then come back to rest. Each segment only needs the
+/// value and slope at its ends in output units so the pieces join smoothly
+/// without working out any polynomial coefficients.
+///
+#[test]
+fn hermite_move() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.hermite_cubic(1.0, (0.0, 0.0), (1.0, 2.0)));
+  fcn1.add_subfunction(factory.hermite_cubic(2.0, (1.0, 2.0), (5.0, 2.0)));
+  fcn1.add_subfunction(factory.hermite_cubic(1.0, (5.0, 2.0), (6.0, 0.0)));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/hermite_move.csv".to_string(),
+  );
+}
+
+/// Helping to understand quintic Hermite segments
+///
+/// The quintic segment also matches curvature at its ends, so a rest to rest
+/// move built from it starts and stops with zero acceleration as well as zero
+/// velocity.
+///
+#[test]
+fn hermite_quintic_move() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.hermite_quintic(1.0, (0.0, 0.0, 0.0), (1.0, 2.0, 0.0)));
+  fcn1.add_subfunction(factory.hermite_quintic(2.0, (1.0, 2.0, 0.0), (5.0, 2.0, 0.0)));
+  fcn1.add_subfunction(factory.hermite_quintic(1.0, (5.0, 2.0, 0.0), (6.0, 0.0, 0.0)));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/hermite_quintic_move.csv".to_string(),
+  );
+}
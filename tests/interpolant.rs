@@ -0,0 +1,24 @@
+use piecewise_fcn_lib as pw;
+
+/// Helping to understand PCHIP and Akima interpolants
+///
+/// The same calibration table with a sharp step is interpolated twice. PCHIP
+/// stays monotonic through the step while Akima follows the data a little more
+/// closely but is allowed to bend. Neither one rings on the flat sections the
+/// way a natural spline would.
+///
+#[test]
+fn interpolant_step() {
+  let factory = pw::Factory;
+  let x = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+  let y = vec![0.0, 0.1, 0.2, 4.0, 4.1, 4.1, 4.2];
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.pchip(6.0, x.clone(), y.clone()).unwrap());
+  fcn1.add_subfunction(factory.akima(6.0, x, y).unwrap());
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/interpolant_step.csv".to_string(),
+  );
+}
@@ -0,0 +1,24 @@
+use piecewise_fcn_lib as pw;
+use pw::operator::inverse::Inverse;
+
+/// Helping to understand calibration lookups
+///
+/// A sensor calibration table is run through a PCHIP interpolant, which stays
+/// monotonic, and then inverted so a reading can be turned back into the
+/// quantity that produced it. The inverse is defined over the readings.
+///
+#[test]
+fn inverse_calibration() {
+  let factory = pw::Factory;
+  let x = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+  let y = vec![0.0, 0.2, 0.9, 2.4, 2.8, 3.0];
+  let mut calibration = pw::Function::new();
+  calibration.add_subfunction(factory.pchip(5.0, x, y).unwrap());
+  let fcn1 = Inverse::with_table(Box::new(calibration), 64).unwrap();
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/inverse_calibration.csv".to_string(),
+  );
+}
@@ -0,0 +1,44 @@
+use piecewise_fcn_lib as pw;
+use pw::factory::noise::Kind;
+
+/// Helping to understand noise kinds
+///
+/// Each kind of noise is generated with the same rate, scale and seed one
+/// after another: uniform, Gaussian, pink, value and Perlin noise.
+///
+#[test]
+fn noise_kinds() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  for kind in [Kind::Uniform, Kind::Gaussian, Kind::Pink, Kind::Value, Kind::Perlin].iter() {
+    fcn1.add_subfunction(factory.noise(1.0, *kind, 50.0, 1.0, 57));
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/noise_kinds.csv".to_string(),
+  );
+}
+
+/// Helping to understand noisy profiles
+///
+/// Noise is stacked on top of a profile by adding it as another function.
+/// Because the noise only depends on the seed this file is identical every
+/// time the test runs.
+///
+#[test]
+fn noise_profile() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.hermite_cubic(2.0, (0.0, 0.0), (1.0, 0.0)));
+  let mut noise = pw::Function::new();
+  noise.add_subfunction(factory.noise(2.0, Kind::Value, 20.0, 0.05, 1));
+  fcn1.add_function(noise);
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/noise_profile.csv".to_string(),
+  );
+}
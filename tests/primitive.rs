@@ -0,0 +1,27 @@
+use piecewise_fcn_lib as pw;
+
+/// Helping to understand primitives
+///
+/// A typical command profile is built only out of primitives: a constant
+/// level, a step up, a ramp back down and a hold of wherever the ramp ended.
+/// The saturating ramp at the end does the same ramp and hold in a single
+/// subfunction.
+///
+#[test]
+fn primitive_profile() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.constant(1.0, 1.0));
+  fcn1.add_subfunction(factory.step(1.0, 0.5, (1.0, 3.0), 3.0));
+  let ramp = factory.ramp(1.0, (3.0, 2.0));
+  let hold = factory.hold(1.0, ramp.as_ref());
+  fcn1.add_subfunction(ramp);
+  fcn1.add_subfunction(hold);
+  fcn1.add_subfunction(factory.saturating_ramp(2.0, (0.5, 1.5), (2.0, 0.0)).unwrap());
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/primitive_profile.csv".to_string(),
+  );
+}
@@ -0,0 +1,32 @@
+use piecewise_fcn_lib as pw;
+
+/// Helping to understand rational functions
+///
+/// A first order low-pass step response approaches its final value along an
+/// asymptote, 1 - 1/(1 + x) here, which no polynomial can follow forever. The
+/// second subfunction is the Padé approximant of e^x next to the truncated
+/// Taylor series it was built from.
+///
+#[test]
+fn rational_asymptote() {
+  let factory = pw::Factory;
+  let taylor = [1.0, 1.0, 0.5, 1.0 / 6.0, 1.0 / 24.0];
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.rational(2.0, (0.0, 20.0), vec![0.0, 1.0], vec![1.0, 1.0], false).unwrap());
+  fcn1.add_subfunction(factory.pade(2.0, (-2.0, 2.0), &taylor, 2, 2, false).unwrap());
+  fcn1.add_subfunction(factory.polynomial(2.0, (-2.0, 2.0), taylor.to_vec(), false));
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/rational_asymptote.csv".to_string(),
+  );
+}
+
+/// Poles inside of the interval are reported instead of generating infinities
+#[test]
+fn rational_pole() {
+  let factory = pw::Factory;
+  let result = factory.rational(1.0, (0.0, 2.0), vec![1.0], vec![-1.0, 1.0], false);
+  assert_eq!(Some(pw::Error::Pole(1.0)), result.err());
+}
@@ -0,0 +1,29 @@
+use piecewise_fcn_lib as pw;
+use pw::factory::table::Reconstruction;
+use pw::sampling::resample;
+
+/// Helping to understand resampling
+///
+/// A square wave made of steps is sampled at 1 kHz and brought down to
+/// 100 Hz. The anti-alias filter removes the harmonics that 100 Hz cannot
+/// carry, which rounds the edges off instead of folding them back as
+/// aliases. The result is reconstructed with a first-order hold into a table
+/// subfunction that can be used like any other.
+///
+#[test]
+fn resample_square() {
+  let factory = pw::Factory;
+  let mut square = pw::Function::new();
+  for _ in 0..4 {
+    square.add_subfunction(factory.step(0.25, 0.125, (1.0, -1.0), -1.0));
+  }
+  let values = resample::resample_function(&square, 1000.0, 1, 10).unwrap();
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.table(100.0, values, Reconstruction::FirstOrder).unwrap());
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/resample_square.csv".to_string(),
+  );
+}
@@ -0,0 +1,31 @@
+use piecewise_fcn_lib as pw;
+use pw::analysis::roots;
+
+/// Helping to understand level crossings
+///
+/// A position profile rises past a safety limit. The first crossing of the
+/// limit is found and a step that turns on at that point is stacked on top,
+/// so the output jumps up exactly where the profile reaches the limit.
+///
+#[test]
+fn roots_first_crossing() {
+  let factory = pw::Factory;
+  let mut profile = pw::Function::new();
+  profile.add_subfunction(factory.smootherstep(2.0, (0.0, 1.0)));
+  profile.add_subfunction(factory.constant(1.0, 1.0));
+  profile.add_subfunction(factory.smootherstep(2.0, (1.0, 0.0)));
+  let crossings = roots::crossings(&profile, 0.8);
+  let first = crossings[0].x();
+
+  let mut fcn1 = pw::Function::new();
+  let mut alarm = pw::Function::new_delay(first);
+  alarm.add_subfunction(factory.constant(5.0 - first, 0.5));
+  fcn1.add_function(profile);
+  fcn1.add_function(alarm);
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/roots_first_crossing.csv".to_string(),
+  );
+}
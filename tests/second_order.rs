@@ -0,0 +1,43 @@
+use piecewise_fcn_lib as pw;
+
+/// Helping to understand damping
+///
+/// Unit step responses with the same natural frequency are stacked one after
+/// another with delays. Light damping rings around the gain, critical damping
+/// is the fastest response without overshoot and heavy damping creeps up to
+/// the gain.
+///
+#[test]
+fn second_order_damping() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  for (idx, damping) in [0.1, 0.5, 1.0, 3.0].iter().enumerate() {
+    let mut step = pw::Function::new_delay(5.0 * idx as f64);
+    step.add_subfunction(factory.second_order(5.0, 2.0 * std::f64::consts::PI, *damping, 1.0, (0.0, 0.0)).unwrap());
+    fcn1.add_function(step);
+  }
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/second_order_damping.csv".to_string(),
+  );
+}
+
+/// Helping to understand free oscillation
+///
+/// With a gain of 0 the response is a damped oscillation that starts from the
+/// initial value and slope and decays to 0.
+///
+#[test]
+fn second_order_free() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.second_order(5.0, 10.0, 0.05, 0.0, (1.0, 5.0)).unwrap());
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/second_order_free.csv".to_string(),
+  );
+}
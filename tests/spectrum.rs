@@ -0,0 +1,33 @@
+use piecewise_fcn_lib as pw;
+use pw::analysis::spectrum::{self, Window};
+
+/// Helping to understand actuator bandwidth
+///
+/// A move with sharp corners is compared with the same move built from
+/// smootherstep segments. The sharp corners spread energy to much higher
+/// frequencies, which is what an actuator with limited bandwidth can not
+/// follow. Both spectra are written out with a Hann window.
+///
+#[test]
+fn spectrum_corners() {
+  let factory = pw::Factory;
+  let mut sharp = pw::Function::new();
+  sharp.add_subfunction(factory.ramp(0.5, (0.0, 1.0)));
+  sharp.add_subfunction(factory.constant(1.0, 1.0));
+  sharp.add_subfunction(factory.ramp(0.5, (1.0, 0.0)));
+  sharp.add_subfunction(factory.constant(2.0, 0.0));
+  let mut smooth = pw::Function::new();
+  smooth.add_subfunction(factory.smootherstep(0.5, (0.0, 1.0)));
+  smooth.add_subfunction(factory.constant(1.0, 1.0));
+  smooth.add_subfunction(factory.smootherstep(0.5, (1.0, 0.0)));
+  smooth.add_subfunction(factory.constant(2.0, 0.0));
+
+  spectrum::write_to_file(
+    &spectrum::spectrum(&sharp, 1000.0, Window::Hann).unwrap(),
+    "tests/output/spectrum_sharp.csv".to_string(),
+  );
+  spectrum::write_to_file(
+    &spectrum::spectrum(&smooth, 1000.0, Window::Hann).unwrap(),
+    "tests/output/spectrum_smooth.csv".to_string(),
+  );
+}
@@ -0,0 +1,36 @@
+use piecewise_fcn_lib as pw;
+use pw::analysis::statistics;
+
+/// Helping to understand mean and RMS
+///
+/// A noisy trapezoid is analysed and constants at its mean, RMS and median
+/// are placed after it for comparison. The RMS sits above the mean because
+/// squaring weighs the large values more.
+///
+#[test]
+fn statistics_report() {
+  let factory = pw::Factory;
+  let mut profile = pw::Function::new();
+  profile.add_subfunction(factory.ramp(1.0, (0.0, 1.0)));
+  profile.add_subfunction(factory.constant(2.0, 1.0));
+  profile.add_subfunction(factory.ramp(1.0, (1.0, 0.0)));
+  let mut noise = pw::Function::new();
+  noise.add_subfunction(factory.noise(4.0, pw::factory::noise::Kind::Value, 20.0, 0.05, 1));
+  profile.add_function(noise);
+  let stats = statistics::statistics(&profile).unwrap();
+  let median = statistics::percentile(&profile, 0.5).unwrap();
+
+  let mut fcn1 = pw::Function::new();
+  let mut levels = pw::Function::new_delay(4.0);
+  levels.add_subfunction(factory.constant(1.0, stats.mean));
+  levels.add_subfunction(factory.constant(1.0, stats.rms));
+  levels.add_subfunction(factory.constant(1.0, median));
+  fcn1.add_function(profile);
+  fcn1.add_function(levels);
+
+  pw::write_to_file(
+    Box::new(fcn1),
+    1000.0,
+    "tests/output/statistics_report.csv".to_string(),
+  );
+}
@@ -0,0 +1,23 @@
+use piecewise_fcn_lib as pw;
+use pw::sampling::adaptive;
+use pw::sampling::stream::{Missing, Sample};
+
+/// Helping to understand streaming samples
+///
+/// A trapezoid profile is streamed at timestamps from a clock that jitters
+/// around 1 kHz, the way a DAC would pull values in real time. Nothing is
+/// allocated up front, every value is generated when it is asked for and
+/// the last value is held if a timestamp falls outside of the profile.
+///
+#[test]
+fn stream_jitter() {
+  let factory = pw::Factory;
+  let mut fcn1 = pw::Function::new();
+  fcn1.add_subfunction(factory.ramp(0.25, (0.0, 1.0)));
+  fcn1.add_subfunction(factory.constant(0.5, 1.0));
+  fcn1.add_subfunction(factory.ramp(0.25, (1.0, 0.0)));
+  let clock = (0..1010).map(|tick| (tick as f64 + 0.3 * (tick as f64 * 0.7).sin()) / 1000.0);
+  let points: Vec<(f64, f64)> = fcn1.samples_at(clock).missing(Missing::Hold).collect();
+
+  adaptive::write_to_file(&points, "tests/output/stream_jitter.csv".to_string());
+}